@@ -0,0 +1,83 @@
+use axum_cometd::{LongPollingServiceContext, LongPollingServiceContextBuilder, RouterBuilder};
+use core::time::Duration;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use serde_json::{json, value::RawValue, Value as JsonValue};
+use std::sync::Arc;
+use test_common::ClientMock;
+use tokio::runtime::Runtime;
+
+const SUBSCRIBERS: [usize; 2] = [100, 1_000];
+
+fn payload() -> JsonValue {
+    json!({
+        "items": (0..50)
+            .map(|i| json!({"id": i, "name": format!("item-{i}"), "tags": ["a", "b", "c"]}))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn setup(
+    runtime: &Runtime,
+    subscribers: usize,
+) -> (Arc<LongPollingServiceContext<(), ()>>, Vec<ClientMock>) {
+    runtime.block_on(async {
+        let context = LongPollingServiceContextBuilder::new()
+            .timeout(Duration::from_secs(5))
+            .max_interval(Duration::from_secs(60))
+            .build();
+        let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+
+        let mut mock_clients = Vec::with_capacity(subscribers);
+        for _ in 0..subscribers {
+            let mut mock_client = ClientMock::create("", "/", "", "", router.clone());
+            mock_client.handshake().await;
+            mock_client.subscribe(&["/topic"]).await.unwrap();
+            mock_clients.push(mock_client);
+        }
+
+        (context, mock_clients)
+    })
+}
+
+async fn receive_all(mock_clients: &[ClientMock]) {
+    for mock_client in mock_clients {
+        assert_eq!(mock_client.connect().await.len(), 1);
+    }
+}
+
+fn fanout(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let payload = payload();
+    let raw_payload: Arc<RawValue> = serde_json::value::to_raw_value(&payload).unwrap().into();
+
+    let mut group = c.benchmark_group("fanout");
+    group.sample_size(10);
+
+    for subscribers in SUBSCRIBERS {
+        let (context, mock_clients) = setup(&runtime, subscribers);
+
+        group.bench_with_input(BenchmarkId::new("send", subscribers), &(), |b, ()| {
+            b.to_async(&runtime).iter(|| async {
+                context.send("/topic", &payload).await.unwrap();
+                receive_all(&mock_clients).await;
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("send_raw", subscribers), &(), |b, ()| {
+            b.to_async(&runtime).iter(|| async {
+                context
+                    .send_raw("/topic", Arc::clone(&raw_payload))
+                    .await
+                    .unwrap();
+                receive_all(&mock_clients).await;
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, fanout);
+criterion_main!(benches);
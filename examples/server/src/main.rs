@@ -36,7 +36,6 @@ async fn main() {
         .max_interval(Duration::from_secs(2))
         .client_channel_capacity(500)
         .client_storage_capacity(10_000)
-        .subscription_channel_capacity(500)
         .subscription_storage_capacity(10_000)
         .build();
 
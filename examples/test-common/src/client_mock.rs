@@ -202,21 +202,40 @@ impl ClientMock {
     pub async fn send_request(&self, endpoint: &str, body: JsonValue) -> Response {
         self.router
             .clone()
-            .oneshot(build_req(endpoint, body))
+            .oneshot(build_req(endpoint, None, body))
+            .await
+            .unwrap()
+    }
+
+    #[inline]
+    pub async fn send_request_with_header(
+        &self,
+        endpoint: &str,
+        header: (&str, &str),
+        body: JsonValue,
+    ) -> Response {
+        self.router
+            .clone()
+            .oneshot(build_req(endpoint, Some(header), body))
             .await
             .unwrap()
     }
 }
 
 #[inline]
-fn build_req(uri: &str, body: JsonValue) -> Request<Body> {
-    Request::builder()
+fn build_req(uri: &str, header: Option<(&str, &str)>, body: JsonValue) -> Request<Body> {
+    let builder = Request::builder()
         .uri(uri)
         .method("POST")
         .header(CONTENT_TYPE, "application/json")
-        .header(COOKIE, format!("BAYEUX_BROWSER={TEST_CLIENT_ID};"))
-        .body(Body::from(body.to_string()))
-        .unwrap()
+        .header(COOKIE, format!("BAYEUX_BROWSER={TEST_CLIENT_ID};"));
+
+    match header {
+        Some((name, value)) => builder.header(name, value),
+        None => builder,
+    }
+    .body(Body::from(body.to_string()))
+    .unwrap()
 }
 
 trait JsonValueExt {
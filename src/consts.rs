@@ -3,5 +3,9 @@ use core::time::Duration;
 pub(crate) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(20);
 pub(crate) const DEFAULT_INTERVAL: Duration = Duration::new(0, 0);
 pub(crate) const DEFAULT_MAX_INTERVAL: Duration = Duration::from_secs(60);
+pub(crate) const DEFAULT_MULTIPLE_CLIENTS_INTERVAL: Duration = Duration::from_millis(1500);
 pub(crate) const DEFAULT_CHANNEL_CAPACITY: usize = 500;
 pub(crate) const DEFAULT_STORAGE_CAPACITY: usize = 10_000;
+pub(crate) const DEFAULT_MAX_REQUEST_SIZE: usize = 2 * 1024 * 1024;
+/// Roughly 30 years, the same as tokio uses for timers without deadline.
+pub(crate) const FAR_FUTURE: Duration = Duration::from_secs(86400 * 365 * 30);
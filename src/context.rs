@@ -1,40 +1,71 @@
 mod build_router;
 mod builder;
-mod subscription_task;
 
 pub use {build_router::*, builder::*};
 
 use crate::{
+    consts::FAR_FUTURE,
     messages::SubscriptionMessage,
-    types::{ChannelId, ClientId, ClientReceiver, ClientSender, CookieId},
-    utils::{ChannelNameValidator, WildNamesCache},
-    CometdCustomDataSender, CometdEventReceiver, Event, SendError,
+    types::{
+        ChannelId, ChannelNameValidator, ClientId, ClientQueueSender, ClientReceiver, ClientSender,
+        CloseReason, Conflation, CookieConfig, CookieId, IdGenerator, PayloadError,
+        PayloadValidator, PublisherTask, RequestBinding, UserIdentity,
+    },
+    utils::{
+        ChannelThrottler, EvictedClients, LongPollsCounter, RateLimitVerdict, RateLimiter,
+        Scheduler, SuppressedWindow, UsersIndex, WildNamesCache,
+    },
+    CometdCustomDataSender, CometdEventReceiver, DeliveryReport, Event, Priority, Publisher,
+    ReconnectAdvice, ScheduleHandle, ScheduledMessage, SendError, SessionAttributes,
+    SessionBinding, SubscribeError,
 };
 use ahash::{HashMap, HashSet, HashSetExt as _};
 use async_broadcast::{InactiveReceiver, Sender};
-use core::{fmt::Debug, ops::Deref};
+use axum::http::HeaderMap;
+use axum_extra::extract::CookieJar;
+use core::{fmt::Debug, ops::Deref, time::Duration};
 use serde::Serialize;
-use serde_json::json;
-use std::{collections::hash_map::Entry, sync::Arc};
-use tokio::sync::{mpsc, RwLock};
+use serde_json::value::RawValue;
+use std::{
+    collections::{hash_map::Entry, VecDeque},
+    sync::{Arc, OnceLock, Weak},
+};
+use tokio::{
+    sync::{mpsc::error::TrySendError, RwLock},
+    time::Instant,
+};
 
 /// Context for sending messages to channels.
 #[derive(Debug)]
 pub struct LongPollingServiceContext<AdditionalData, CustomData> {
+    pub(crate) this: Weak<Self>,
     pub(crate) tx: Sender<Arc<Event<AdditionalData, CustomData>>>,
     pub(crate) inactive_rx: InactiveReceiver<Arc<Event<AdditionalData, CustomData>>>,
 
     pub(crate) wildnames_cache: WildNamesCache,
-    pub(crate) channel_name_validator: ChannelNameValidator,
+    pub(crate) long_polls_counter: LongPollsCounter,
+    pub(crate) evicted_clients: EvictedClients,
+    pub(crate) users_index: UsersIndex,
+    pub(crate) user_identity: Option<UserIdentity>,
+    pub(crate) payload_validators: HashMap<ChannelId, Vec<PayloadValidator>>,
+    pub(crate) conflation: HashMap<ChannelId, Conflation>,
+    pub(crate) message_ttls: HashMap<ChannelId, Duration>,
+    pub(crate) throttler: ChannelThrottler,
+    pub(crate) channel_name_validator: Arc<dyn ChannelNameValidator>,
+    pub(crate) id_generator: Box<dyn IdGenerator>,
     pub(crate) consts: LongPollingServiceContextConsts,
+    pub(crate) cookie_config: CookieConfig,
+    pub(crate) session_binding: SessionBinding,
+    pub(crate) rate_limiter: RateLimiter,
+    pub(crate) publisher: OnceLock<(Publisher, Arc<PublisherTask>)>,
+    pub(crate) scheduler: Scheduler,
     pub(crate) channels_data: RwLock<HashMap<ChannelId, Channel>>,
     client_id_senders: Arc<RwLock<HashMap<ClientId, ClientSender>>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub(crate) struct Channel {
     client_ids: HashSet<ClientId>,
-    tx: mpsc::Sender<SubscriptionMessage>,
 }
 
 impl Channel {
@@ -42,11 +73,6 @@ impl Channel {
     const fn client_ids(&self) -> &HashSet<ClientId> {
         &self.client_ids
     }
-
-    #[inline(always)]
-    pub(crate) const fn tx(&self) -> &mpsc::Sender<SubscriptionMessage> {
-        &self.tx
-    }
 }
 
 impl<AdditionalData, CustomData> LongPollingServiceContext<AdditionalData, CustomData> {
@@ -86,6 +112,10 @@ impl<AdditionalData, CustomData> LongPollingServiceContext<AdditionalData, Custo
 
     /// Send message to channel.
     ///
+    /// Doesn't wait for slow subscribers: subscribers with full queue don't get message,
+    /// they are counted in [`DeliveryReport::dropped`] and [`DeliveryReport::blocked`].
+    /// See [`LongPollingServiceContextBuilder::client_channel_capacity`].
+    ///
     /// # Example
     /// ```rust,no_run
     /// # use core::time::Duration;
@@ -101,7 +131,6 @@ impl<AdditionalData, CustomData> LongPollingServiceContext<AdditionalData, Custo
     ///         .timeout(Duration::from_secs(1))
     ///         .max_interval(Duration::from_secs(2))
     ///         .client_channel_capacity(10_000)
-    ///         .subscription_channel_capacity(20_000)
     ///         .build::<(), ()>();
     ///
     ///     loop {
@@ -125,39 +154,577 @@ impl<AdditionalData, CustomData> LongPollingServiceContext<AdditionalData, Custo
         &self,
         channel: &str,
         message: impl Debug + Serialize,
-    ) -> Result<(), SendError> {
+    ) -> Result<DeliveryReport, SendError> {
+        self.send_raw(channel, serialize(&message)?).await
+    }
+
+    /// Send message to channel, waiting for subscriptions lock at most `timeout`.
+    /// Delivery itself never waits for slow subscribers, see [`DeliveryReport::blocked`].
+    /// Return [`SendError::Full`] if timeout elapsed, message isn't delivered to anybody then.
+    #[inline]
+    pub async fn send_timeout(
+        &self,
+        channel: &str,
+        message: impl Debug + Serialize,
+        timeout: Duration,
+    ) -> Result<DeliveryReport, SendError> {
+        tokio::time::timeout(timeout, self.send(channel, message))
+            .await
+            .map_err(|_| SendError::Full)?
+    }
+
+    /// Send already serialized JSON message to channel.
+    /// Message is shared between all subscribers without copying.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # async {
+    /// # let context = axum_cometd::LongPollingServiceContextBuilder::new().build::<(), ()>();
+    /// let message = serde_json::value::RawValue::from_string(r#"{"msg":"Hello"}"#.to_owned())?;
+    ///
+    /// context.send_raw("/topic", message.into()).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # };
+    /// ```
+    #[inline]
+    pub async fn send_raw(
+        &self,
+        channel: &str,
+        message: Arc<RawValue>,
+    ) -> Result<DeliveryReport, SendError> {
+        self.send_raw_with_options(channel, message, None, Priority::Normal)
+            .await
+    }
+
+    /// Send message to channel, which is dropped from client queues after `ttl`.
+    /// Overrides TTL of channel, see [`LongPollingServiceContextBuilder::message_ttl`].
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # async {
+    /// # let context = axum_cometd::LongPollingServiceContextBuilder::new().build::<(), ()>();
+    /// context
+    ///     .send_with_ttl(
+    ///         "/rides/42",
+    ///         "Your ride is arriving",
+    ///         core::time::Duration::from_secs(30),
+    ///     )
+    ///     .await?;
+    /// # Ok::<(), axum_cometd::SendError>(())
+    /// # };
+    /// ```
+    #[inline]
+    pub async fn send_with_ttl(
+        &self,
+        channel: &str,
+        message: impl Debug + Serialize,
+        ttl: Duration,
+    ) -> Result<DeliveryReport, SendError> {
+        self.send_raw_with_options(channel, serialize(&message)?, Some(ttl), Priority::Normal)
+            .await
+    }
+
+    /// Send message to channel with given delivery `priority`.
+    /// Urgent message is delivered on the next `/meta/connect` before pending normal messages.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # async {
+    /// # let context = axum_cometd::LongPollingServiceContextBuilder::new().build::<(), ()>();
+    /// context
+    ///     .send_with_priority(
+    ///         "/announcements",
+    ///         "Maintenance in 1 minute",
+    ///         axum_cometd::Priority::Urgent,
+    ///     )
+    ///     .await?;
+    /// # Ok::<(), axum_cometd::SendError>(())
+    /// # };
+    /// ```
+    #[inline]
+    pub async fn send_with_priority(
+        &self,
+        channel: &str,
+        message: impl Debug + Serialize,
+        priority: Priority,
+    ) -> Result<DeliveryReport, SendError> {
+        self.send_raw_with_options(channel, serialize(&message)?, None, priority)
+            .await
+    }
+
+    #[inline]
+    async fn send_raw_with_options(
+        &self,
+        channel: &str,
+        message: Arc<RawValue>,
+        ttl: Option<Duration>,
+        priority: Priority,
+    ) -> Result<DeliveryReport, SendError> {
         self.channel_name_validator
             .validate_send_channel_name(channel)
             .then_some(())
             .ok_or(SendError::InvalidChannel)?;
 
-        let subscription_message = SubscriptionMessage {
-            channel: channel.to_owned(),
-            msg: json!(message),
+        self.flush_throttles().await;
+        if self.throttle(channel, &message) {
+            return Ok(DeliveryReport {
+                throttled: true,
+                ..Default::default()
+            });
+        }
+
+        let (channels, recipients) = self.recipients(channel).await;
+        let mut report = DeliveryReport {
+            channels,
+            ..Default::default()
         };
-        let wildnames = self.wildnames_cache.fetch_wildnames(channel);
-        let read_guard = self.channels_data.read().await;
-        for channel in core::iter::once(channel).chain(wildnames.iter().map(String::deref)) {
-            if let Some(tx) = read_guard.get(channel).map(Channel::tx) {
-                tx.send(subscription_message.clone()).await?;
-            } else {
-                tracing::warn!(
-                    channel = channel,
-                    "No `{channel}` channel was found for message: `{message:?}`."
-                );
-            }
+
+        if recipients.is_empty() {
+            tracing::warn!(
+                channel = channel,
+                "No subscribers of `{channel}` channel were found for message: `{message}`."
+            );
         }
 
-        Ok(())
+        let message = SubscriptionMessage {
+            priority,
+            ..self.subscription_message(channel, message, ttl)
+        };
+        deliver(&mut report, recipients, &message);
+
+        Ok(report)
+    }
+
+    /// Send message to several channels.
+    /// Message is serialized once and subscribers are collected under single lock.
+    /// Repeated `channels` are sent once and [`DeliveryReport::channels`] has no duplicates.
+    ///
+    /// Every copy of message carries its own channel, so client, which matches several of
+    /// `channels` (e.g. through wildcard), gets message once per channel and is counted
+    /// in [`DeliveryReport::recipients`] once per channel too.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # async {
+    /// # let context = axum_cometd::LongPollingServiceContextBuilder::new().build::<(), ()>();
+    /// let report = context
+    ///     .send_many(&["/users/alice", "/users/bob"], "Hello")
+    ///     .await?;
+    /// println!("Message was delivered to {} sessions", report.recipients);
+    /// # Ok::<(), axum_cometd::SendError>(())
+    /// # };
+    /// ```
+    #[inline]
+    pub async fn send_many(
+        &self,
+        channels: &[impl AsRef<str>],
+        message: impl Debug + Serialize,
+    ) -> Result<DeliveryReport, SendError> {
+        if !channels.iter().all(|channel| {
+            self.channel_name_validator
+                .validate_send_channel_name(channel.as_ref())
+        }) {
+            return Err(SendError::InvalidChannel);
+        }
+
+        let message = serialize(&message)?;
+        self.flush_throttles().await;
+        let mut report = DeliveryReport::default();
+        let mut unique_channels = HashSet::with_capacity(channels.len());
+        let channels = channels
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(|channel| unique_channels.insert(*channel))
+            .filter(|channel| {
+                let throttled = self.throttle(channel, &message);
+                report.throttled |= throttled;
+                !throttled
+            })
+            .collect::<Vec<_>>();
+        let wildnames = channels
+            .iter()
+            .map(|channel| self.wildnames_cache.fetch_wildnames(channel))
+            .collect::<Vec<_>>();
+
+        let deliveries = {
+            // Lock order must be the same as in `subscribe`.
+            let client_id_senders_read_guard = self.client_id_senders.read().await;
+            let channels_data_read_guard = self.channels_data.read().await;
+
+            channels
+                .iter()
+                .zip(&wildnames)
+                .map(|(channel, wildnames)| {
+                    let (channels, recipients) = collect_recipients(
+                        &client_id_senders_read_guard,
+                        &channels_data_read_guard,
+                        channel,
+                        wildnames,
+                    );
+                    for channel in channels {
+                        if !report.channels.contains(&channel) {
+                            report.channels.push(channel);
+                        }
+                    }
+
+                    (*channel, recipients)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        for (channel, recipients) in deliveries {
+            let message = self.subscription_message(channel, Arc::clone(&message), None);
+            deliver(&mut report, recipients, &message);
+        }
+
+        Ok(report)
+    }
+
+    /// Send message direct to several clients.
+    /// Message is serialized once and clients are looked up under single lock.
+    /// Clients, which weren't found, are counted as [`DeliveryReport::dropped`].
+    ///
+    /// Message gets [`Priority::Normal`] and TTL configured for `channel` in builder:
+    /// per-message TTL and priority aren't supported by direct sends to several clients.
+    #[inline]
+    pub async fn send_to_clients(
+        &self,
+        channel: &str,
+        client_ids: &[ClientId],
+        message: impl Debug + Serialize,
+    ) -> Result<DeliveryReport, SendError> {
+        self.channel_name_validator
+            .validate_send_channel_name(channel)
+            .then_some(())
+            .ok_or(SendError::InvalidChannel)?;
+
+        let message = self.subscription_message(channel, serialize(&message)?, None);
+        let mut report = DeliveryReport::default();
+        let recipients = {
+            let client_id_senders_read_guard = self.client_id_senders.read().await;
+
+            client_ids
+                .iter()
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .filter_map(|client_id| {
+                    if let Some(client) = client_id_senders_read_guard.get(client_id) {
+                        Some((*client_id, client.sender()))
+                    } else {
+                        tracing::warn!(
+                            client_id = %client_id,
+                            "No `{client_id}` client was found for message: `{}`.",
+                            message.msg
+                        );
+                        report.dropped += 1;
+
+                        None
+                    }
+                })
+                .collect()
+        };
+
+        deliver(&mut report, recipients, &message);
+
+        Ok(report)
+    }
+
+    /// Send message direct to every connected client, regardless of subscriptions.
+    ///
+    /// Message gets [`Priority::Normal`] and TTL configured for `channel` in builder:
+    /// per-message TTL and priority aren't supported by broadcast.
+    #[inline]
+    pub async fn broadcast_all(
+        &self,
+        channel: &str,
+        message: impl Debug + Serialize,
+    ) -> Result<DeliveryReport, SendError> {
+        self.channel_name_validator
+            .validate_send_channel_name(channel)
+            .then_some(())
+            .ok_or(SendError::InvalidChannel)?;
+
+        let message = self.subscription_message(channel, serialize(&message)?, None);
+        let recipients = self
+            .client_id_senders
+            .read()
+            .await
+            .iter()
+            .map(|(client_id, client)| (*client_id, client.sender()))
+            .collect();
+
+        let mut report = DeliveryReport::default();
+        deliver(&mut report, recipients, &message);
+
+        Ok(report)
+    }
+
+    /// Send message to every subscriber of `channel`, for which `predicate` returns `true`.
+    /// Predicate gets client id and session attributes, including handshake `AdditionalData`.
+    /// Sessions, which aren't subscribed on `channel` directly or through wildcard, are skipped.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # async {
+    /// # #[derive(Debug, Clone)]
+    /// # struct HandshakeData { region: String }
+    /// # let context = axum_cometd::LongPollingServiceContextBuilder::new().build::<HandshakeData, ()>();
+    /// let report = context
+    ///     .send_where(
+    ///         "/news",
+    ///         |_, attributes| {
+    ///             attributes
+    ///                 .get::<HandshakeData>()
+    ///                 .is_some_and(|data| data.region == "eu")
+    ///         },
+    ///         "Hello, Europe",
+    ///     )
+    ///     .await?;
+    /// println!("{} sessions matched", report.recipients + report.dropped);
+    /// # Ok::<(), axum_cometd::SendError>(())
+    /// # };
+    /// ```
+    #[inline]
+    pub async fn send_where(
+        &self,
+        channel: &str,
+        predicate: impl Fn(&ClientId, &SessionAttributes) -> bool,
+        message: impl Debug + Serialize,
+    ) -> Result<DeliveryReport, SendError> {
+        self.channel_name_validator
+            .validate_send_channel_name(channel)
+            .then_some(())
+            .ok_or(SendError::InvalidChannel)?;
+
+        let message = self.subscription_message(channel, serialize(&message)?, None);
+        let wildnames = self.wildnames_cache.fetch_wildnames(channel);
+        let (channels, subscribers) = {
+            // Lock order must be the same as in `subscribe`.
+            let client_id_senders_read_guard = self.client_id_senders.read().await;
+            let channels_data_read_guard = self.channels_data.read().await;
+
+            let (channels, client_ids) =
+                collect_subscribers(&channels_data_read_guard, channel, &wildnames);
+            let subscribers = client_ids
+                .into_iter()
+                .filter_map(|client_id| {
+                    client_id_senders_read_guard.get(client_id).map(|client| {
+                        (*client_id, client.sender(), Arc::clone(client.attributes()))
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            (channels, subscribers)
+        };
+
+        // Predicate is called without locks, so it may use this context.
+        let recipients = subscribers
+            .into_iter()
+            .filter_map(|(client_id, tx, attributes)| {
+                predicate(&client_id, &attributes).then_some((client_id, tx))
+            })
+            .collect();
+
+        let mut report = DeliveryReport {
+            channels,
+            ..Default::default()
+        };
+        deliver(&mut report, recipients, &message);
+
+        Ok(report)
+    }
+
+    /// Enqueue message to channel without waiting.
+    /// Return [`SendError::Full`] if [`Publisher`] queue is full.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # async {
+    /// # let context = axum_cometd::LongPollingServiceContextBuilder::new().build::<(), ()>();
+    /// match context.try_send("/topic", "Hello") {
+    ///     Ok(()) | Err(axum_cometd::SendError::Full) => {}
+    ///     Err(error) => panic!("{error}"),
+    /// }
+    /// # };
+    /// ```
+    #[inline]
+    pub fn try_send(
+        self: &Arc<Self>,
+        channel: &str,
+        message: impl Debug + Serialize,
+    ) -> Result<(), SendError>
+    where
+        AdditionalData: Send + Sync + 'static,
+        CustomData: Send + Sync + 'static,
+    {
+        self.publisher().try_send(channel, message)
+    }
+
+    /// Send message to channel at `send_at`.
+    /// Channel name is validated and message is serialized immediately.
+    /// Scheduled messages are dropped with context.
+    /// Must be called within tokio runtime.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # async {
+    /// # let context = axum_cometd::LongPollingServiceContextBuilder::new().build::<(), ()>();
+    /// let send_at = tokio::time::Instant::now() + core::time::Duration::from_secs(60);
+    /// let handle = context.send_at("/reminders", "Meeting starts", send_at)?;
+    ///
+    /// // Meeting was cancelled.
+    /// handle.cancel();
+    /// # Ok::<(), axum_cometd::SendError>(())
+    /// # };
+    /// ```
+    #[inline]
+    pub fn send_at(
+        &self,
+        channel: &str,
+        message: impl Debug + Serialize,
+        send_at: Instant,
+    ) -> Result<ScheduleHandle, SendError>
+    where
+        AdditionalData: Send + Sync + 'static,
+        CustomData: Send + Sync + 'static,
+    {
+        self.channel_name_validator
+            .validate_send_channel_name(channel)
+            .then_some(())
+            .ok_or(SendError::InvalidChannel)?;
+        let message = serialize(&message)?;
+
+        Ok(self.scheduler.schedule(channel, message, send_at, |id| {
+            let context = Weak::clone(&self.this);
+
+            tokio::spawn(async move {
+                tokio::time::sleep_until(send_at).await;
+
+                let Some(context) = context.upgrade() else {
+                    return;
+                };
+                let Some(entry) = context.scheduler.take(id) else {
+                    return;
+                };
+                if let Err(error) = context.send_raw(&entry.channel, entry.msg).await {
+                    tracing::error!(
+                        channel = &*entry.channel,
+                        "Scheduled message wasn't sent: {error}."
+                    );
+                }
+                context.arm_throttle_timer();
+            })
+            .abort_handle()
+        }))
+    }
+
+    /// Send message to channel after `delay`, see [`LongPollingServiceContext::send_at`].
+    /// Delay, which overflows [`Instant`], is clamped to roughly 30 years.
+    #[inline]
+    pub fn send_after(
+        &self,
+        channel: &str,
+        message: impl Debug + Serialize,
+        delay: Duration,
+    ) -> Result<ScheduleHandle, SendError>
+    where
+        AdditionalData: Send + Sync + 'static,
+        CustomData: Send + Sync + 'static,
+    {
+        let now = Instant::now();
+        let send_at = now.checked_add(delay).unwrap_or_else(|| now + FAR_FUTURE);
+
+        self.send_at(channel, message, send_at)
+    }
+
+    /// List messages, which are scheduled but not sent yet, ordered by send time.
+    #[inline]
+    pub fn scheduled_messages(&self) -> Vec<ScheduledMessage> {
+        self.scheduler.list()
+    }
+
+    /// Get [`Publisher`] handle of this context.
+    /// Background delivery task is spawned on first call, so it must be called within tokio runtime.
+    #[inline]
+    pub fn publisher(self: &Arc<Self>) -> Publisher
+    where
+        AdditionalData: Send + Sync + 'static,
+        CustomData: Send + Sync + 'static,
+    {
+        self.publisher
+            .get_or_init(|| Publisher::spawn(self, self.consts.publisher_channel_capacity))
+            .0
+            .clone()
+    }
+
+    /// Return `true` if somebody is subscribed on `channel` directly or through wildcard.
+    #[inline]
+    pub async fn has_subscribers(&self, channel: &str) -> bool {
+        let wildnames = self.wildnames_cache.fetch_wildnames(channel);
+        let channels_data_read_guard = self.channels_data.read().await;
+
+        core::iter::once(channel)
+            .chain(wildnames.iter().map(String::deref))
+            .filter_map(|channel| channels_data_read_guard.get(channel))
+            .any(|channel| !channel.client_ids().is_empty())
+    }
+
+    /// Collect senders of clients subscribed on `channel` or matching wildcard channels
+    /// and names of matched channels.
+    /// Every client is returned once, even if it matches several subscriptions.
+    #[inline]
+    async fn recipients(&self, channel: &str) -> (Vec<String>, Vec<(ClientId, ClientQueueSender)>) {
+        let wildnames = self.wildnames_cache.fetch_wildnames(channel);
+
+        // Lock order must be the same as in `subscribe`.
+        let client_id_senders_read_guard = self.client_id_senders.read().await;
+        let channels_data_read_guard = self.channels_data.read().await;
+
+        collect_recipients(
+            &client_id_senders_read_guard,
+            &channels_data_read_guard,
+            channel,
+            &wildnames,
+        )
     }
 
     /// Send message direct to client.
+    ///
+    /// Doesn't wait for client: return [`SendError::Full`] if client queue is full,
+    /// message is dropped then.
+    /// See [`LongPollingServiceContextBuilder::client_channel_capacity`].
     #[inline]
     pub async fn send_to_client(
         &self,
         channel: &str,
         client_id: &ClientId,
         msg: impl Debug + Serialize,
+    ) -> Result<(), SendError> {
+        self.send_raw_to_client(channel, client_id, serialize(&msg)?, Priority::Normal)
+            .await
+    }
+
+    /// Send message direct to client with given delivery `priority`,
+    /// see [`LongPollingServiceContext::send_with_priority`].
+    #[inline]
+    pub async fn send_to_client_with_priority(
+        &self,
+        channel: &str,
+        client_id: &ClientId,
+        msg: impl Debug + Serialize,
+        priority: Priority,
+    ) -> Result<(), SendError> {
+        self.send_raw_to_client(channel, client_id, serialize(&msg)?, priority)
+            .await
+    }
+
+    #[inline]
+    async fn send_raw_to_client(
+        &self,
+        channel: &str,
+        client_id: &ClientId,
+        msg: Arc<RawValue>,
+        priority: Priority,
     ) -> Result<(), SendError> {
         self.channel_name_validator
             .validate_send_channel_name(channel)
@@ -165,24 +732,104 @@ impl<AdditionalData, CustomData> LongPollingServiceContext<AdditionalData, Custo
             .ok_or(SendError::InvalidChannel)?;
 
         if let Some(tx) = self.client_id_senders.read().await.get(client_id) {
-            tx.send(SubscriptionMessage {
-                channel: channel.to_owned(),
-                msg: json!(msg),
-            })
-            .await?;
+            tx.try_send(SubscriptionMessage {
+                priority,
+                ..self.subscription_message(channel, msg, None)
+            })?;
 
             Ok(())
         } else {
             tracing::warn!(
                 client_id = %client_id,
-                "No `{client_id}` client was found for message: `{msg:?}`."
+                "No `{client_id}` client was found for message: `{msg}`."
             );
 
             Err(SendError::ClientWasntFound(*client_id))
         }
     }
 
-    pub(crate) async fn register(self: &Arc<Self>, cookie_id: CookieId) -> Option<ClientId>
+    /// Send message direct to all sessions of application user.
+    /// Return number of sessions, which got message.
+    /// Sessions with full queue don't get message, like in [`LongPollingServiceContext::send`].
+    ///
+    /// User identity is extracted at handshake,
+    /// see [`LongPollingServiceContextBuilder::user_identity`].
+    ///
+    /// [`LongPollingServiceContextBuilder::user_identity`]: crate::LongPollingServiceContextBuilder::user_identity
+    #[inline]
+    pub async fn send_to_user(
+        &self,
+        user: &str,
+        channel: &str,
+        msg: impl Debug + Serialize,
+    ) -> Result<usize, SendError> {
+        self.channel_name_validator
+            .validate_send_channel_name(channel)
+            .then_some(())
+            .ok_or(SendError::InvalidChannel)?;
+
+        let message = self.subscription_message(channel, serialize(&msg)?, None);
+        let recipients = {
+            let client_id_senders_read_guard = self.client_id_senders.read().await;
+
+            self.users_index
+                .client_ids(user)
+                .into_iter()
+                .filter_map(|client_id| {
+                    client_id_senders_read_guard
+                        .get(&client_id)
+                        .map(|client| (client_id, client.sender()))
+                })
+                .collect()
+        };
+
+        let mut report = DeliveryReport::default();
+        deliver(&mut report, recipients, &message);
+
+        Ok(report.recipients)
+    }
+
+    /// Evict all sessions of application user with [`ReconnectAdvice::None`],
+    /// so clients don't re-handshake. Return number of removed sessions.
+    #[inline]
+    pub async fn disconnect_user(self: &Arc<Self>, user: &str) -> usize {
+        let mut count = 0;
+
+        for client_id in self.users_index.client_ids(user) {
+            if self
+                .disconnect_client(client_id, ReconnectAdvice::None)
+                .await
+            {
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    /// Return `clientId`s of all sessions of application user.
+    #[inline]
+    pub fn user_sessions(&self, user: &str) -> Vec<ClientId> {
+        self.users_index.client_ids(user)
+    }
+
+    /// Return attributes of client session or `None` if client wasn't found.
+    #[inline]
+    pub async fn session(&self, client_id: &ClientId) -> Option<Arc<SessionAttributes>> {
+        self.client_id_senders
+            .read()
+            .await
+            .get(client_id)
+            .map(|client| Arc::clone(client.attributes()))
+    }
+
+    pub(crate) async fn register(
+        self: &Arc<Self>,
+        cookie_id: CookieId,
+        attributes: Arc<SessionAttributes>,
+        user: Option<String>,
+        local: bool,
+    ) -> Option<ClientId>
     where
         AdditionalData: Send + Sync + 'static,
         CustomData: Send + Sync + 'static,
@@ -190,19 +837,23 @@ impl<AdditionalData, CustomData> LongPollingServiceContext<AdditionalData, Custo
         let client_id = {
             let mut client_id_channels_write_guard = self.client_id_senders.write().await;
 
-            let client_id = ClientId::gen();
-            let (tx, rx) = mpsc::channel(self.consts.client_channel_capacity);
+            let client_id = ClientId::gen(&*self.id_generator);
+            let user = user.map(Arc::<str>::from);
 
             match client_id_channels_write_guard.entry(client_id) {
                 Entry::Occupied(_) => return None,
                 Entry::Vacant(v) => {
+                    if let Some(ref user) = user {
+                        self.users_index.insert(Arc::clone(user), client_id);
+                    }
+
                     v.insert(ClientSender::create(
                         Arc::clone(self),
                         cookie_id,
                         client_id,
-                        self.consts.max_interval,
-                        tx,
-                        rx,
+                        attributes,
+                        user,
+                        local,
                     ));
                 }
             }
@@ -218,54 +869,310 @@ impl<AdditionalData, CustomData> LongPollingServiceContext<AdditionalData, Custo
         Some(client_id)
     }
 
-    pub(crate) async fn subscribe(self: &Arc<Self>, client_id: ClientId, channels: &[String])
+    pub(crate) async fn subscribe(
+        self: &Arc<Self>,
+        client_id: ClientId,
+        channels: &[String],
+    ) -> Result<(), SubscribeError>
     where
         AdditionalData: Send + Sync + 'static,
         CustomData: Send + Sync + 'static,
     {
+        // Lock order must be the same as in `recipients`.
+        let client_id_senders_read_guard = self.client_id_senders.read().await;
+        let client = client_id_senders_read_guard
+            .get(&client_id)
+            .ok_or(SubscribeError::ClientWasntFound(client_id))?;
         let mut channels_data_write_guard = self.channels_data.write().await;
-        for channel in channels {
+
+        let unique_channels = channels.iter().collect::<HashSet<_>>();
+        let new_subscriptions = unique_channels
+            .iter()
+            .filter(|&&channel| {
+                !channels_data_write_guard
+                    .get(channel)
+                    .is_some_and(|data| data.client_ids.contains(&client_id))
+            })
+            .count();
+        let new_channels = unique_channels
+            .iter()
+            .filter(|&&channel| !channels_data_write_guard.contains_key(channel))
+            .count();
+
+        if let Some(max) = self.consts.max_subscriptions_per_client {
+            (client.subscriptions_count() + new_subscriptions <= max)
+                .then_some(())
+                .ok_or(SubscribeError::TooManySubscriptions)?;
+        }
+        if let Some(max) = self.consts.max_channels {
+            (channels_data_write_guard.len() + new_channels <= max)
+                .then_some(())
+                .ok_or(SubscribeError::TooManyChannels)?;
+        }
+
+        for channel in unique_channels {
             match channels_data_write_guard.entry(channel.clone()) {
                 Entry::Occupied(o) => o.into_mut(),
                 Entry::Vacant(v) => {
-                    let (tx, rx) = mpsc::channel(self.consts.subscription_channel_capacity);
-
-                    subscription_task::spawn(channel.clone(), rx, Arc::clone(self));
                     tracing::info!(
                         channel = channel,
                         "New subscription ({channel}) channel was registered."
                     );
 
-                    v.insert(Channel {
-                        client_ids: Default::default(),
-                        tx,
-                    })
+                    v.insert(Channel::default())
                 }
             }
             .client_ids
             .insert(client_id);
         }
+        client.add_subscriptions(new_subscriptions);
 
         tracing::info!(
             client_id = %client_id,
             channels = debug(channels),
             "Client with clientId `{client_id}` subscribe on `{channels:?}` channels."
         );
+
+        Ok(())
+    }
+
+    /// Subscribe client on channels on its behalf.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # async fn f(context: std::sync::Arc<axum_cometd::LongPollingServiceContext<(), ()>>, client_id: axum_cometd::ClientId) {
+    /// context
+    ///     .subscribe_client(client_id, &["/orders/42".to_owned()])
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn subscribe_client(
+        self: &Arc<Self>,
+        client_id: ClientId,
+        channels: &[String],
+    ) -> Result<(), SubscribeError>
+    where
+        AdditionalData: Send + Sync + 'static,
+        CustomData: Send + Sync + 'static,
+    {
+        channels
+            .iter()
+            .all(|channel| {
+                self.channel_name_validator
+                    .validate_subscribe_channel_name(channel)
+            })
+            .then_some(())
+            .ok_or(SubscribeError::InvalidChannel)?;
+
+        self.subscribe(client_id, channels).await?;
+
+        let _ = self
+            .tx
+            .broadcast(Arc::new(Event::ServerSubscribe {
+                client_id,
+                channels: channels.to_vec(),
+            }))
+            .await;
+
+        Ok(())
+    }
+
+    /// Unsubscribe client from channels on its behalf.
+    #[inline]
+    pub async fn unsubscribe_client(
+        &self,
+        client_id: ClientId,
+        channels: &[String],
+    ) -> Result<(), SubscribeError> {
+        channels
+            .iter()
+            .all(|channel| {
+                self.channel_name_validator
+                    .validate_subscribe_channel_name(channel)
+            })
+            .then_some(())
+            .ok_or(SubscribeError::InvalidChannel)?;
+
+        {
+            // Lock order must be the same as in `recipients`.
+            let client_id_senders_read_guard = self.client_id_senders.read().await;
+            let client = client_id_senders_read_guard
+                .get(&client_id)
+                .ok_or(SubscribeError::ClientWasntFound(client_id))?;
+            let mut channels_data_write_guard = self.channels_data.write().await;
+
+            let mut removed_subscriptions = 0;
+            let mut removed_channels = HashSet::new();
+            for channel in channels.iter().collect::<HashSet<_>>() {
+                let Some(data) = channels_data_write_guard.get_mut(channel) else {
+                    continue;
+                };
+
+                if data.client_ids.remove(&client_id) {
+                    removed_subscriptions += 1;
+                }
+                if data.client_ids.is_empty() {
+                    tracing::info!(
+                        channel = channel,
+                        "Channel `{channel}` have no active subscriber. Eliminate channel."
+                    );
+                    channels_data_write_guard.remove(channel);
+                    removed_channels.insert(channel.clone());
+                }
+            }
+            client.remove_subscriptions(removed_subscriptions);
+            self.wildnames_cache.remove_wildnames(removed_channels);
+        }
+
+        tracing::info!(
+            client_id = %client_id,
+            channels = debug(channels),
+            "Client with clientId `{client_id}` unsubscribe from `{channels:?}` channels."
+        );
+
+        let _ = self
+            .tx
+            .broadcast(Arc::new(Event::ServerUnsubscribe {
+                client_id,
+                channels: channels.to_vec(),
+            }))
+            .await;
+
+        Ok(())
     }
 
     // TODO: Spawn task and send unsubscribe command through channel?
     /// Remove client.
     #[inline]
     pub async fn unsubscribe(self: &Arc<Self>, client_id: ClientId) {
-        tokio::join!(
+        let ((), attributes) = tokio::join!(
             self.remove_client_id_from_subscriptions(&client_id),
             self.remove_client_tx(&client_id),
         );
 
         let _ = self
             .tx
-            .broadcast(Arc::new(Event::SessionRemoved { client_id }))
+            .broadcast(Arc::new(Event::SessionRemoved {
+                client_id,
+                attributes: attributes.unwrap_or_default(),
+            }))
+            .await;
+    }
+
+    /// Evict client: answer pending `/meta/connect` with given reconnect advice and remove client.
+    /// Client, which isn't waiting on `/meta/connect`, gets advice on the next connect
+    /// within `max_interval`. Return `false` if client wasn't found.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use core::time::Duration;
+    /// # use axum_cometd::{ClientId, ReconnectAdvice};
+    /// # async fn evict(client_id: ClientId) {
+    /// # let context = axum_cometd::LongPollingServiceContextBuilder::new().build::<(), ()>();
+    ///     context
+    ///         .disconnect_client(client_id, ReconnectAdvice::Retry(Duration::from_secs(30)))
+    ///         .await;
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn disconnect_client(
+        self: &Arc<Self>,
+        client_id: ClientId,
+        advice: ReconnectAdvice,
+    ) -> bool {
+        if !self
+            .close_client(&client_id, CloseReason::Evicted(advice))
+            .await
+        {
+            tracing::warn!(
+                client_id = %client_id,
+                "Can't find client `{client_id}`. Can't evict."
+            );
+            return false;
+        }
+
+        let now = Instant::now();
+        self.evicted_clients.insert(
+            client_id,
+            advice,
+            now.checked_add(self.consts.max_interval)
+                .unwrap_or_else(|| now + FAR_FUTURE),
+        );
+
+        tracing::info!(
+            client_id = %client_id,
+            advice = debug(advice),
+            "Client `{client_id}` was evicted with advice `{advice:?}`."
+        );
+
+        let _ = self
+            .tx
+            .broadcast(Arc::new(Event::SessionEvicted { client_id, advice }))
             .await;
+
+        self.unsubscribe(client_id).await;
+
+        true
+    }
+
+    /// Account message in rate limiter. Return `false` if message is over limit.
+    /// Evict client if it exceeded limits too many times.
+    #[inline]
+    pub(crate) async fn check_rate_limit(
+        self: &Arc<Self>,
+        client_id: ClientId,
+        cookie_id: CookieId,
+        bytes: usize,
+    ) -> bool {
+        let verdict = self.rate_limiter.check(client_id, cookie_id, bytes);
+        self.handle_rate_limit_verdict(client_id, verdict).await
+    }
+
+    /// Account rate limit violation, which was detected by handler.
+    #[inline]
+    pub(crate) async fn report_rate_limit_violation(self: &Arc<Self>, client_id: ClientId) {
+        let verdict = self.rate_limiter.report_violation(client_id);
+        self.handle_rate_limit_verdict(client_id, verdict).await;
+    }
+
+    #[inline]
+    async fn handle_rate_limit_verdict(
+        self: &Arc<Self>,
+        client_id: ClientId,
+        verdict: RateLimitVerdict,
+    ) -> bool {
+        match verdict {
+            RateLimitVerdict::Allowed => true,
+            RateLimitVerdict::Exceeded => {
+                tracing::warn!(
+                    client_id = %client_id,
+                    "Client `{client_id}` exceeded rate limit."
+                );
+                false
+            }
+            RateLimitVerdict::Evict(advice) => {
+                tracing::warn!(
+                    client_id = %client_id,
+                    "Client `{client_id}` exceeded rate limit too many times. Evict."
+                );
+                self.disconnect_client(client_id, advice).await;
+                false
+            }
+        }
+    }
+
+    /// Wake up pending `/meta/connect` of client with given reason.
+    /// Return `false` if client wasn't found.
+    #[inline]
+    pub(crate) async fn close_client(&self, client_id: &ClientId, reason: CloseReason) -> bool {
+        self.client_id_senders
+            .read()
+            .await
+            .get(client_id)
+            .map(|tx| tx.close(reason))
+            .is_some()
     }
 
     #[inline]
@@ -274,12 +1181,10 @@ impl<AdditionalData, CustomData> LongPollingServiceContext<AdditionalData, Custo
         // TODO: Replace on LinkedList?
         let mut removed_channels = HashSet::new();
 
-        self.channels_data.write().await.retain(
-            |channel,
-             &mut Channel {
-                 ref mut client_ids,
-                 tx: _,
-             }| {
+        self.channels_data
+            .write()
+            .await
+            .retain(|channel, &mut Channel { ref mut client_ids }| {
                 if client_ids.remove(client_id) {
                     tracing::info!(
                         client_id = %client_id,
@@ -298,21 +1203,20 @@ impl<AdditionalData, CustomData> LongPollingServiceContext<AdditionalData, Custo
                 } else {
                     true
                 }
-            },
-        );
+            });
 
         self.wildnames_cache.remove_wildnames(removed_channels);
     }
 
     #[inline]
-    async fn remove_client_tx(&self, client_id: &ClientId) {
-        if self
-            .client_id_senders
-            .write()
-            .await
-            .remove(client_id)
-            .is_some()
-        {
+    async fn remove_client_tx(&self, client_id: &ClientId) -> Option<Arc<SessionAttributes>> {
+        let tx = self.client_id_senders.write().await.remove(client_id);
+
+        if let Some(ref tx) = tx {
+            self.rate_limiter.remove(client_id, &tx.cookie_id());
+            if let Some(user) = tx.user() {
+                self.users_index.remove(user, client_id);
+            }
             tracing::info!(
                 client_id = %client_id,
                 "Client `{client_id}` was unsubscribed."
@@ -323,21 +1227,181 @@ impl<AdditionalData, CustomData> LongPollingServiceContext<AdditionalData, Custo
                 "Can't find client `{client_id}`. Can't unsubscribed."
             );
         }
+
+        tx.map(|tx| Arc::clone(tx.attributes()))
     }
 
     #[inline]
     pub(crate) async fn check_client(
         &self,
-        cookie_id: CookieId,
+        binding: RequestBinding,
         client_id: &ClientId,
-    ) -> Option<()> {
-        self.client_id_senders
+        token: Option<&str>,
+    ) -> Option<CookieId> {
+        let stored_cookie_id = self
+            .client_id_senders
             .read()
             .await
             .get(client_id)
-            .map(ClientSender::cookie_id)
-            .eq(&Some(cookie_id))
-            .then_some(())
+            .map(ClientSender::cookie_id)?;
+
+        match binding {
+            RequestBinding::Browser(cookie_id) => stored_cookie_id.eq(&cookie_id),
+            RequestBinding::ClientId => self.session_binding.verify(client_id, token),
+        }
+        .then_some(stored_cookie_id)
+    }
+
+    /// Count message in channel throttle. Return `true` if message must be suppressed.
+    #[inline]
+    fn throttle(&self, channel: &str, message: &Arc<RawValue>) -> bool {
+        if self.throttler.is_empty() {
+            return false;
+        }
+
+        let wildnames = self.wildnames_cache.fetch_wildnames(channel);
+        let patterns = core::iter::once(channel).chain(wildnames.iter().map(String::deref));
+
+        let suppressed = self
+            .throttler
+            .check(channel, patterns, message, Instant::now());
+        if suppressed {
+            tracing::debug!(
+                channel = channel,
+                "Message `{message}` to `{channel}` channel was suppressed by throttle."
+            );
+        }
+
+        suppressed
+    }
+
+    /// Send merged messages and report suppressed messages of throttle windows, which ended.
+    /// Called on every send, so it doesn't require spawning tasks.
+    #[inline]
+    async fn flush_throttles(&self) {
+        if self.throttler.is_empty() {
+            return;
+        }
+
+        for SuppressedWindow {
+            channel,
+            count,
+            latest,
+        } in self.throttler.take_ended(Instant::now())
+        {
+            if let Some(latest) = latest {
+                // Merged message starts the next window.
+                if !self.throttle(&channel, &latest) {
+                    let (_, recipients) = self.recipients(&channel).await;
+                    let message = self.subscription_message(&channel, latest, None);
+                    deliver(&mut DeliveryReport::default(), recipients, &message);
+                }
+            }
+
+            let _ = self
+                .tx
+                .broadcast(Arc::new(Event::MessagesSuppressed { channel, count }))
+                .await;
+        }
+    }
+
+    /// Spawn timer, which flushes throttle windows with suppressed messages,
+    /// so they don't wait for the next send.
+    /// Called from client requests and background tasks, which already require `Send` data.
+    #[inline]
+    pub(crate) fn arm_throttle_timer(&self)
+    where
+        AdditionalData: Send + Sync + 'static,
+        CustomData: Send + Sync + 'static,
+    {
+        if self.throttler.is_empty() {
+            return;
+        }
+        let Some(flush_at) = self.throttler.arm_timer() else {
+            return;
+        };
+
+        let context = Weak::clone(&self.this);
+
+        tokio::task::spawn(async move {
+            tokio::time::sleep_until(flush_at).await;
+
+            let Some(context) = context.upgrade() else {
+                return;
+            };
+            context.throttler.disarm_timer(flush_at);
+            context.flush_throttles().await;
+            context.arm_throttle_timer();
+        });
+    }
+
+    /// Construct message for client queues.
+    /// Without `ttl`, TTL of `channel` or matching wildcard channel is used.
+    #[inline]
+    fn subscription_message(
+        &self,
+        channel: &str,
+        msg: Arc<RawValue>,
+        ttl: Option<Duration>,
+    ) -> SubscriptionMessage {
+        let wildnames = (!self.conflation.is_empty()
+            || (ttl.is_none() && !self.message_ttls.is_empty()))
+        .then(|| self.wildnames_cache.fetch_wildnames(channel));
+        let patterns = || {
+            core::iter::once(channel).chain(
+                wildnames
+                    .iter()
+                    .flat_map(|wildnames| wildnames.iter().map(String::deref)),
+            )
+        };
+
+        let conflation_key = patterns()
+            .find_map(|pattern| self.conflation.get(pattern))
+            .and_then(|conflation| conflation.key(channel, &msg));
+        let ttl = ttl.or_else(|| {
+            patterns()
+                .find_map(|pattern| self.message_ttls.get(pattern))
+                .copied()
+        });
+
+        SubscriptionMessage {
+            channel: Arc::from(channel),
+            msg,
+            conflation_key,
+            // TTL, which overflows `Instant`, means no expiry.
+            expires_at: ttl.and_then(|ttl| Instant::now().checked_add(ttl)),
+            priority: Priority::Normal,
+        }
+    }
+
+    /// Validate `data` with validators of `channel` and matching wildcard channels.
+    #[inline]
+    pub(crate) fn validate_payload(
+        &self,
+        channel: &str,
+        data: &RawValue,
+    ) -> Result<(), PayloadError> {
+        if self.payload_validators.is_empty() {
+            return Ok(());
+        }
+
+        let wildnames = self.wildnames_cache.fetch_wildnames(channel);
+
+        core::iter::once(channel)
+            .chain(wildnames.iter().map(String::deref))
+            .filter_map(|channel| self.payload_validators.get(channel))
+            .flatten()
+            .try_for_each(|validator| validator.validate(data))
+    }
+
+    #[inline(always)]
+    pub(crate) fn request_binding(
+        &self,
+        jar: &CookieJar,
+        headers: &HeaderMap,
+    ) -> Option<RequestBinding> {
+        self.session_binding
+            .extract(&self.cookie_config.name, jar, headers)
     }
 
     #[inline]
@@ -349,3 +1413,101 @@ impl<AdditionalData, CustomData> LongPollingServiceContext<AdditionalData, Custo
             .map(ClientSender::subscribe)
     }
 }
+
+#[inline]
+pub(crate) fn serialize(message: &(impl Debug + Serialize)) -> Result<Arc<RawValue>, SendError> {
+    serde_json::value::to_raw_value(message)
+        .map(Arc::from)
+        .map_err(|error| {
+            tracing::error!("Can't serialize message `{message:?}`: {error}.");
+            SendError::InvalidMessage
+        })
+}
+
+/// Collect senders of clients subscribed on `channel` or `wildnames` and names of matched channels.
+/// Every client is returned once, even if it matches several subscriptions.
+#[inline]
+fn collect_recipients(
+    client_id_senders: &HashMap<ClientId, ClientSender>,
+    channels_data: &HashMap<ChannelId, Channel>,
+    channel: &str,
+    wildnames: &VecDeque<ChannelId>,
+) -> (Vec<String>, Vec<(ClientId, ClientQueueSender)>) {
+    let (channels, client_ids) = collect_subscribers(channels_data, channel, wildnames);
+
+    let recipients = client_ids
+        .into_iter()
+        .filter_map(|client_id| {
+            client_id_senders
+                .get(client_id)
+                .map(|client| (*client_id, client.sender()))
+        })
+        .collect();
+
+    (channels, recipients)
+}
+
+/// Collect channels, which match `channel` directly or through wildcard, and their subscribers.
+#[inline]
+fn collect_subscribers<'a>(
+    channels_data: &'a HashMap<ChannelId, Channel>,
+    channel: &str,
+    wildnames: &VecDeque<ChannelId>,
+) -> (Vec<String>, HashSet<&'a ClientId>) {
+    core::iter::once(channel)
+        .chain(wildnames.iter().map(String::deref))
+        .filter_map(|channel| {
+            channels_data
+                .get(channel)
+                .map(|data| (channel.to_owned(), data.client_ids()))
+        })
+        .fold(
+            Default::default(),
+            |(mut channels, mut client_ids), (channel, channel_client_ids)| {
+                channels.push(channel);
+                client_ids.extend(channel_client_ids);
+                (channels, client_ids)
+            },
+        )
+}
+
+/// Deliver `message` to `recipients` and update `report`.
+/// Never waits: message is dropped for sessions with full queue,
+/// so one stalled session doesn't block publishers.
+#[inline]
+fn deliver(
+    report: &mut DeliveryReport,
+    recipients: Vec<(ClientId, ClientQueueSender)>,
+    message: &SubscriptionMessage,
+) {
+    let channel = &*message.channel;
+
+    for (client_id, tx) in recipients {
+        tracing::trace!(
+            client_id = %client_id,
+            channel = channel,
+            "Message `{message:?}` from channel `{channel}` was sent to client `{client_id}`."
+        );
+
+        match tx.try_send(message.clone()) {
+            Ok(()) => report.recipients += 1,
+            Err(TrySendError::Full(_)) => {
+                report.dropped += 1;
+                report.blocked += 1;
+                tracing::warn!(
+                    client_id = %client_id,
+                    channel = channel,
+                    "Queue of client `{client_id}` is full, message was dropped."
+                );
+            }
+            Err(TrySendError::Closed(_)) => {
+                report.dropped += 1;
+                tracing::error!(
+                    client_id = %client_id,
+                    channel = channel,
+                    "Channel was closed!"
+                );
+            }
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{handlers::*, LongPollingServiceContext};
-use axum::{routing::post, Extension, Router};
+use axum::{extract::DefaultBodyLimit, routing::post, Extension, Router};
 use core::fmt::Debug;
 use std::sync::Arc;
 
@@ -89,6 +89,8 @@ impl RouterBuilder {
             disconnect_base_path,
         } = self;
 
+        let body_limit = DefaultBodyLimit::max(context.consts.max_request_size);
+
         Router::new()
             .route(subscribe_base_path, post(subscribe))
             .route(&format!("{handshake_base_path}/handshake"), post(handshake))
@@ -97,6 +99,7 @@ impl RouterBuilder {
                 &format!("{disconnect_base_path}/disconnect"),
                 post(disconnect),
             )
+            .layer(body_limit)
             .with_state(context)
     }
 
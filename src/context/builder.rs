@@ -1,8 +1,21 @@
-use crate::{consts::*, LongPollingServiceContext};
+use crate::{
+    consts::*,
+    types::{ChannelId, Conflation, CookieConfig, PayloadValidator, UserIdentity},
+    utils::{ChannelThrottler, RateLimiter},
+    ChannelNameRules, ChannelNameValidator, IdGenerator, LongPollingServiceContext, RateLimit,
+    ReconnectAdvice, SameSite, SessionAttributes, SessionBinding, Throttle, TimestampIdGenerator,
+    TypedChannel,
+};
 use ahash::{HashMap, HashMapExt as _};
 use async_broadcast::broadcast;
-use core::time::Duration;
-use std::sync::Arc;
+use axum::http::HeaderMap;
+use core::{fmt::Debug, time::Duration};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value as JsonValue;
+use std::{
+    borrow::Cow,
+    sync::{Arc, OnceLock, Weak},
+};
 use tokio::sync::RwLock;
 
 /// A builder to construct `LongPoolingServiceContext`.
@@ -12,6 +25,16 @@ pub struct LongPollingServiceContextBuilder {
     subscriptions_storage_capacity: usize,
     client_ids_storage_capacity: usize,
     consts: LongPollingServiceContextConsts,
+    cookie_config: CookieConfig,
+    session_binding: SessionBinding,
+    rate_limiter: RateLimiter,
+    channel_name_validator: Arc<dyn ChannelNameValidator>,
+    id_generator: Box<dyn IdGenerator>,
+    user_identity: Option<UserIdentity>,
+    payload_validators: HashMap<ChannelId, Vec<PayloadValidator>>,
+    conflation: HashMap<ChannelId, Conflation>,
+    message_ttls: HashMap<ChannelId, Duration>,
+    throttler: ChannelThrottler,
 }
 
 impl Default for LongPollingServiceContextBuilder {
@@ -22,6 +45,16 @@ impl Default for LongPollingServiceContextBuilder {
             subscriptions_storage_capacity: DEFAULT_STORAGE_CAPACITY,
             client_ids_storage_capacity: DEFAULT_STORAGE_CAPACITY,
             consts: Default::default(),
+            cookie_config: Default::default(),
+            session_binding: Default::default(),
+            rate_limiter: Default::default(),
+            channel_name_validator: Arc::new(ChannelNameRules::new()),
+            id_generator: Box::new(TimestampIdGenerator),
+            user_identity: None,
+            payload_validators: HashMap::new(),
+            conflation: HashMap::new(),
+            message_ttls: HashMap::new(),
+            throttler: Default::default(),
         }
     }
 }
@@ -31,8 +64,15 @@ pub(crate) struct LongPollingServiceContextConsts {
     pub(crate) timeout: Duration,
     pub(crate) interval: Duration,
     pub(crate) max_interval: Duration,
+    pub(crate) max_sessions_per_browser: Option<usize>,
+    pub(crate) max_message_size: Option<usize>,
+    pub(crate) max_batch_length: Option<usize>,
+    pub(crate) max_request_size: usize,
+    pub(crate) max_subscriptions_per_client: Option<usize>,
+    pub(crate) max_channels: Option<usize>,
+    pub(crate) multiple_clients_interval: Duration,
     pub(crate) client_channel_capacity: usize,
-    pub(crate) subscription_channel_capacity: usize,
+    pub(crate) publisher_channel_capacity: usize,
 }
 
 impl Default for LongPollingServiceContextConsts {
@@ -42,8 +82,15 @@ impl Default for LongPollingServiceContextConsts {
             timeout: DEFAULT_TIMEOUT,
             interval: DEFAULT_INTERVAL,
             max_interval: DEFAULT_MAX_INTERVAL,
+            max_sessions_per_browser: None,
+            max_message_size: None,
+            max_batch_length: None,
+            max_request_size: DEFAULT_MAX_REQUEST_SIZE,
+            max_subscriptions_per_client: None,
+            max_channels: None,
+            multiple_clients_interval: DEFAULT_MULTIPLE_CLIENTS_INTERVAL,
             client_channel_capacity: DEFAULT_CHANNEL_CAPACITY,
-            subscription_channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+            publisher_channel_capacity: DEFAULT_CHANNEL_CAPACITY,
         }
     }
 }
@@ -72,17 +119,44 @@ impl LongPollingServiceContextBuilder {
             subscriptions_storage_capacity,
             client_ids_storage_capacity,
             consts,
+            cookie_config,
+            session_binding,
+            rate_limiter,
+            channel_name_validator,
+            id_generator,
+            user_identity,
+            payload_validators,
+            conflation,
+            message_ttls,
+            throttler,
         } = self;
 
+        cookie_config.check();
+
         let (tx, mut rx) = broadcast(events_channel_capacity);
         rx.set_await_active(false);
 
-        Arc::new(LongPollingServiceContext {
+        Arc::new_cyclic(|this| LongPollingServiceContext {
+            this: Weak::clone(this),
             tx,
             inactive_rx: rx.deactivate(),
             wildnames_cache: Default::default(),
-            channel_name_validator: Default::default(),
+            long_polls_counter: Default::default(),
+            evicted_clients: Default::default(),
+            users_index: Default::default(),
+            user_identity,
+            payload_validators,
+            conflation,
+            message_ttls,
+            throttler,
+            channel_name_validator,
+            id_generator,
             consts,
+            cookie_config,
+            session_binding,
+            rate_limiter,
+            publisher: OnceLock::new(),
+            scheduler: Default::default(),
             channels_data: RwLock::new(HashMap::with_capacity(subscriptions_storage_capacity)),
             client_id_senders: Arc::new(RwLock::new(HashMap::with_capacity(
                 client_ids_storage_capacity,
@@ -120,6 +194,71 @@ impl LongPollingServiceContextBuilder {
         self
     }
 
+    /// Set maximum number of pending `/meta/connect` per `BAYEUX_BROWSER`.
+    /// Extra connects will be answered immediately with `multiple-clients` advice.
+    /// Unlimited by default.
+    #[inline(always)]
+    #[must_use]
+    pub const fn max_sessions_per_browser(mut self, max_sessions: usize) -> Self {
+        self.consts.max_sessions_per_browser = Some(max_sessions);
+        self
+    }
+
+    /// Set interval which is advised to clients exceeded `max_sessions_per_browser`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn multiple_clients_interval(mut self, interval: Duration) -> Self {
+        self.consts.multiple_clients_interval = interval;
+        self
+    }
+
+    /// Set maximum size of published message `data` in bytes.
+    /// Bigger messages will be answered with `413::message_too_large` error.
+    #[inline(always)]
+    #[must_use]
+    pub const fn max_message_size(mut self, size: usize) -> Self {
+        self.consts.max_message_size = Some(size);
+        self
+    }
+
+    /// Set maximum number of messages in one `/connect` request.
+    /// Request is parsed only up to the first message over limit and answered with
+    /// `413::batch_too_large` error. Too big batch counts as one rate limit violation of its
+    /// client, see [`LongPollingServiceContextBuilder::evict_on_rate_limit`].
+    #[inline(always)]
+    #[must_use]
+    pub const fn max_batch_length(mut self, length: usize) -> Self {
+        self.consts.max_batch_length = Some(length);
+        self
+    }
+
+    /// Set maximum size of request body in bytes, default is 2 MB.
+    /// Bigger requests will be answered with `413 Payload Too Large` status.
+    #[inline(always)]
+    #[must_use]
+    pub const fn max_request_size(mut self, size: usize) -> Self {
+        self.consts.max_request_size = size;
+        self
+    }
+
+    /// Set maximum number of subscriptions per client.
+    /// Subscribes over limit will be answered with `403::too_many_subscriptions` error.
+    #[inline(always)]
+    #[must_use]
+    pub const fn max_subscriptions_per_client(mut self, max_subscriptions: usize) -> Self {
+        self.consts.max_subscriptions_per_client = Some(max_subscriptions);
+        self
+    }
+
+    /// Set maximum number of channels with subscribers.
+    /// Subscribes over limit will be answered with `403::too_many_channels` error.
+    #[inline(always)]
+    #[must_use]
+    pub const fn max_channels(mut self, max_channels: usize) -> Self {
+        self.consts.max_channels = Some(max_channels);
+        self
+    }
+
     /// Set capacity of event channel.
     #[inline(always)]
     #[must_use]
@@ -129,6 +268,12 @@ impl LongPollingServiceContextBuilder {
     }
 
     /// Set capacity of internal client channels.
+    /// Capacity is shared by messages of all priorities.
+    /// Sends never wait for slow clients: messages for client with full channel are dropped,
+    /// see [`DeliveryReport::blocked`] and [`SendError::Full`].
+    ///
+    /// [`DeliveryReport::blocked`]: crate::DeliveryReport::blocked
+    /// [`SendError::Full`]: crate::SendError::Full
     #[inline(always)]
     #[must_use]
     pub const fn client_channel_capacity(mut self, capacity: usize) -> Self {
@@ -136,6 +281,16 @@ impl LongPollingServiceContextBuilder {
         self
     }
 
+    /// Set capacity of [`Publisher`] queue.
+    ///
+    /// [`Publisher`]: crate::Publisher
+    #[inline(always)]
+    #[must_use]
+    pub const fn publisher_channel_capacity(mut self, capacity: usize) -> Self {
+        self.consts.publisher_channel_capacity = capacity;
+        self
+    }
+
     /// Set capacity of internal client channels storage.
     #[inline(always)]
     #[must_use]
@@ -144,11 +299,14 @@ impl LongPollingServiceContextBuilder {
         self
     }
 
-    /// Set capacity of internal subscription channels.
+    /// Has no effect: messages are delivered directly to client queues,
+    /// which are bounded by [`LongPollingServiceContextBuilder::client_channel_capacity`].
+    #[deprecated(
+        note = "messages are delivered directly to client queues, use `client_channel_capacity`"
+    )]
     #[inline(always)]
     #[must_use]
-    pub const fn subscription_channel_capacity(mut self, capacity: usize) -> Self {
-        self.consts.subscription_channel_capacity = capacity;
+    pub const fn subscription_channel_capacity(self, _capacity: usize) -> Self {
         self
     }
 
@@ -159,4 +317,364 @@ impl LongPollingServiceContextBuilder {
         self.subscriptions_storage_capacity = capacity;
         self
     }
+
+    /// Set name of `BAYEUX_BROWSER` cookie.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use core::time::Duration;
+    /// use axum_cometd::{LongPollingServiceContextBuilder, SameSite};
+    ///
+    /// let context = LongPollingServiceContextBuilder::new()
+    ///     .cookie_name("MY_BROWSER")
+    ///     .cookie_path("/notifications")
+    ///     .cookie_domain("example.com")
+    ///     .cookie_secure(true)
+    ///     .cookie_http_only(true)
+    ///     .cookie_same_site(SameSite::None)
+    ///     .cookie_max_age(Duration::from_secs(24 * 60 * 60))
+    ///     .build::<(), ()>();
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn cookie_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.cookie_config.name = name.into();
+        self
+    }
+
+    /// Set `Path` attribute of `BAYEUX_BROWSER` cookie.
+    #[inline(always)]
+    #[must_use]
+    pub fn cookie_path(mut self, path: impl Into<Cow<'static, str>>) -> Self {
+        self.cookie_config.path = Some(path.into());
+        self
+    }
+
+    /// Set `Domain` attribute of `BAYEUX_BROWSER` cookie.
+    #[inline(always)]
+    #[must_use]
+    pub fn cookie_domain(mut self, domain: impl Into<Cow<'static, str>>) -> Self {
+        self.cookie_config.domain = Some(domain.into());
+        self
+    }
+
+    /// Set `Secure` attribute of `BAYEUX_BROWSER` cookie.
+    #[inline(always)]
+    #[must_use]
+    pub const fn cookie_secure(mut self, secure: bool) -> Self {
+        self.cookie_config.secure = secure;
+        self
+    }
+
+    /// Set `HttpOnly` attribute of `BAYEUX_BROWSER` cookie.
+    #[inline(always)]
+    #[must_use]
+    pub const fn cookie_http_only(mut self, http_only: bool) -> Self {
+        self.cookie_config.http_only = http_only;
+        self
+    }
+
+    /// Set `SameSite` attribute of `BAYEUX_BROWSER` cookie.
+    /// Browsers reject `SameSite::None` cookies without `Secure` attribute,
+    /// so [`LongPollingServiceContextBuilder::cookie_secure`] must be enabled too.
+    #[inline(always)]
+    #[must_use]
+    pub const fn cookie_same_site(mut self, same_site: SameSite) -> Self {
+        self.cookie_config.same_site = Some(same_site);
+        self
+    }
+
+    /// Set `Max-Age` attribute of `BAYEUX_BROWSER` cookie.
+    #[inline(always)]
+    #[must_use]
+    pub const fn cookie_max_age(mut self, max_age: Duration) -> Self {
+        self.cookie_config.max_age = Some(max_age);
+        self
+    }
+
+    /// Set how server bind `clientId` to requests. By default, `BAYEUX_BROWSER` cookie is used.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::sync::Arc;
+    /// use axum::http::HeaderName;
+    /// use axum_cometd::{LongPollingServiceContextBuilder, SessionBinding};
+    ///
+    /// let context = LongPollingServiceContextBuilder::new()
+    ///     .session_binding(SessionBinding::Header(HeaderName::from_static("x-device-id")))
+    ///     .build::<(), ()>();
+    ///
+    /// let context = LongPollingServiceContextBuilder::new()
+    ///     .session_binding(SessionBinding::ClientId {
+    ///         secret: Some(Arc::from(b"super secret".as_slice())),
+    ///     })
+    ///     .build::<(), ()>();
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn session_binding(mut self, session_binding: SessionBinding) -> Self {
+        self.session_binding = session_binding;
+        self
+    }
+
+    /// Set channel names validator. By default, [`ChannelNameRules::new`] is used.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use axum_cometd::{ChannelNameRules, LongPollingServiceContextBuilder};
+    ///
+    /// let context = LongPollingServiceContextBuilder::new()
+    ///     .channel_name_validator(ChannelNameRules::new().wildcards(false))
+    ///     .build::<(), ()>();
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn channel_name_validator(
+        mut self,
+        channel_name_validator: impl ChannelNameValidator + 'static,
+    ) -> Self {
+        self.channel_name_validator = Arc::new(channel_name_validator);
+        self
+    }
+
+    /// Set generator of `clientId` and `BAYEUX_BROWSER` cookie values.
+    /// By default, [`TimestampIdGenerator`] is used.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use axum_cometd::{LongPollingServiceContextBuilder, RandomIdGenerator};
+    ///
+    /// let context = LongPollingServiceContextBuilder::new()
+    ///     .id_generator(RandomIdGenerator)
+    ///     .build::<(), ()>();
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn id_generator(mut self, id_generator: impl IdGenerator + 'static) -> Self {
+        self.id_generator = Box::new(id_generator);
+        self
+    }
+
+    /// Set function to extract application user identity at handshake from request headers,
+    /// handshake `ext` field and session attributes.
+    /// Sessions of user can be reached through [`LongPollingServiceContext::send_to_user`]
+    /// and [`LongPollingServiceContext::disconnect_user`].
+    ///
+    /// Headers and `ext` are supplied by client, so identity must be taken only from values
+    /// verified by server, otherwise any client can impersonate any user.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use axum_cometd::LongPollingServiceContextBuilder;
+    ///
+    /// let context = LongPollingServiceContextBuilder::new()
+    ///     .user_identity(|headers, _ext, _attributes| {
+    ///         // UNSAFE unless server runs behind authenticating reverse proxy,
+    ///         // which sets `x-user-id` and strips it from client requests.
+    ///         headers
+    ///             .get("x-user-id")
+    ///             .and_then(|value| value.to_str().ok())
+    ///             .map(str::to_owned)
+    ///     })
+    ///     .build::<(), ()>();
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn user_identity(
+        mut self,
+        extract: impl Fn(&HeaderMap, Option<&JsonValue>, &SessionAttributes) -> Option<String>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.user_identity = Some(UserIdentity::new(extract));
+        self
+    }
+
+    /// Register typed channel.
+    /// Client publishes to this channel, which can't be deserialized into `T`,
+    /// will be answered with `400::invalid_payload` error.
+    #[inline(always)]
+    #[must_use]
+    pub fn typed_channel<T>(mut self, channel: &TypedChannel<T>) -> Self
+    where
+        T: Debug + Serialize + DeserializeOwned,
+    {
+        let (name, validator) = channel.validator();
+        self.payload_validators
+            .entry(name.to_owned())
+            .or_default()
+            .push(validator);
+        self
+    }
+
+    /// Register JSON Schema of message `data` for `channel`, which may contain wildcards.
+    /// Client publishes, which don't match schemas of published channel or matching wildcard
+    /// channels, will be answered with `400:<path>:<message>` error.
+    /// Schemas and [`TypedChannel`] of the same channel are all checked.
+    ///
+    /// Available with `json-schema` feature.
+    ///
+    /// # Errors
+    /// Return [`SchemaError`] if `schema` isn't valid JSON Schema.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use axum_cometd::LongPollingServiceContextBuilder;
+    /// use serde_json::json;
+    ///
+    /// let context = LongPollingServiceContextBuilder::new()
+    ///     .json_schema(
+    ///         "/prices/*",
+    ///         &json!({
+    ///             "type": "object",
+    ///             "properties": { "value": { "type": "number" } },
+    ///             "required": ["value"],
+    ///         }),
+    ///     )?
+    ///     .build::<(), ()>();
+    /// # Ok::<(), axum_cometd::SchemaError>(())
+    /// ```
+    ///
+    /// [`SchemaError`]: crate::SchemaError
+    #[cfg(feature = "json-schema")]
+    #[inline]
+    pub fn json_schema(
+        mut self,
+        channel: impl Into<String>,
+        schema: &JsonValue,
+    ) -> Result<Self, crate::SchemaError> {
+        let channel = channel.into();
+        let validator = jsonschema::validator_for(schema).map_err(|error| crate::SchemaError {
+            channel: channel.clone(),
+            message: error.to_string(),
+        })?;
+
+        self.payload_validators
+            .entry(channel)
+            .or_default()
+            .push(PayloadValidator::JsonSchema(Box::new(validator)));
+        Ok(self)
+    }
+
+    /// Conflate messages of `channel`, which may contain wildcards.
+    /// Client, which didn't receive previous message of the same channel yet,
+    /// will get only the newest one.
+    ///
+    /// Conflation is applied when message is enqueued, so conflated channel takes at most
+    /// one place per key in client queue and never fills it.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use axum_cometd::LongPollingServiceContextBuilder;
+    ///
+    /// let context = LongPollingServiceContextBuilder::new()
+    ///     .conflate("/prices/*")
+    ///     .build::<(), ()>();
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn conflate(mut self, channel: impl Into<String>) -> Self {
+        self.conflation
+            .insert(channel.into(), Conflation::channel());
+        self
+    }
+
+    /// Conflate messages of `channel` with the same key extracted from message data.
+    /// Messages without key aren't conflated.
+    /// See [`LongPollingServiceContextBuilder::conflate`].
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use axum_cometd::LongPollingServiceContextBuilder;
+    ///
+    /// let context = LongPollingServiceContextBuilder::new()
+    ///     .conflate_by("/prices", |data| data["symbol"].as_str().map(str::to_owned))
+    ///     .build::<(), ()>();
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn conflate_by(
+        mut self,
+        channel: impl Into<String>,
+        key: impl Fn(&JsonValue) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.conflation
+            .insert(channel.into(), Conflation::by_key(key));
+        self
+    }
+
+    /// Set time-to-live of messages sent to `channel`, which may contain wildcards.
+    /// Messages, which weren't received by client in time, are dropped from its queue.
+    /// Can be overridden with [`LongPollingServiceContext::send_with_ttl`].
+    #[inline(always)]
+    #[must_use]
+    pub fn message_ttl(mut self, channel: impl Into<String>, ttl: Duration) -> Self {
+        self.message_ttls.insert(channel.into(), ttl);
+        self
+    }
+
+    /// Throttle messages of `channel`, which may contain wildcards.
+    /// Every channel matching pattern is throttled separately.
+    /// Only channel publishes are throttled: targeted sends, like
+    /// [`LongPollingServiceContext::send_to_client`], aren't counted, so they never become
+    /// merged message of other subscribers.
+    ///
+    /// [`LongPollingServiceContext::send_to_client`]: crate::LongPollingServiceContext::send_to_client
+    ///
+    /// Number of suppressed messages is reported with [`Event::MessagesSuppressed`]
+    /// after the end of interval: by the next send or by timer, which is armed on client
+    /// requests, [`Publisher`] and scheduled messages.
+    ///
+    /// [`Publisher`]: crate::Publisher
+    ///
+    /// [`Event::MessagesSuppressed`]: crate::Event::MessagesSuppressed
+    #[inline(always)]
+    #[must_use]
+    pub fn throttle(mut self, channel: impl Into<String>, throttle: Throttle) -> Self {
+        self.throttler.throttles.insert(channel.into(), throttle);
+        self
+    }
+
+    /// Set rate limit of publishes and subscribes per `clientId`.
+    /// Messages over limit will be answered with `429::rate_limit_exceeded` error.
+    #[inline(always)]
+    #[must_use]
+    pub const fn client_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limiter.client_limit = Some(rate_limit);
+        self
+    }
+
+    /// Set rate limit of publishes and subscribes per `BAYEUX_BROWSER`.
+    /// Messages over limit will be answered with `429::rate_limit_exceeded` error.
+    #[inline(always)]
+    #[must_use]
+    pub const fn browser_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limiter.browser_limit = Some(rate_limit);
+        self
+    }
+
+    /// Evict client after `max_violations` consecutive rate limit violations.
+    /// Too big batches are counted even if no rate limit is set,
+    /// see [`LongPollingServiceContextBuilder::max_batch_length`].
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use axum_cometd::{LongPollingServiceContextBuilder, RateLimit, ReconnectAdvice};
+    ///
+    /// let context = LongPollingServiceContextBuilder::new()
+    ///     .client_rate_limit(RateLimit::new().messages_per_second(10))
+    ///     .evict_on_rate_limit(5, ReconnectAdvice::None)
+    ///     .build::<(), ()>();
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub const fn evict_on_rate_limit(
+        mut self,
+        max_violations: usize,
+        advice: ReconnectAdvice,
+    ) -> Self {
+        self.rate_limiter.eviction = Some((max_violations, advice));
+        self
+    }
 }
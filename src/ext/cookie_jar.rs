@@ -1,13 +1,13 @@
-use crate::types::{CookieId, BAYEUX_BROWSER};
+use crate::types::CookieId;
 use axum_extra::extract::{cookie::Cookie, CookieJar};
 
 pub(crate) trait CookieJarExt {
-    fn get_cookie_id(&self) -> Option<CookieId>;
+    fn get_cookie_id(&self, name: &str) -> Option<CookieId>;
 }
 
 impl CookieJarExt for CookieJar {
-    fn get_cookie_id(&self) -> Option<CookieId> {
-        self.get(BAYEUX_BROWSER)
+    fn get_cookie_id(&self, name: &str) -> Option<CookieId> {
+        self.get(name)
             .map(Cookie::value)
             .map(CookieId::parse)
             .and_then(Result::ok)
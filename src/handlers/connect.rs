@@ -1,8 +1,18 @@
 mod publish;
 mod wait_message;
 
-use crate::{error::HandlerResult, messages::Message, LongPollingServiceContext};
-use axum::{extract::State, Json};
+use crate::{
+    error::HandlerResult,
+    messages::{Batch, Message},
+    types::RequestBinding,
+    LongPollingServiceContext,
+};
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{header::CONTENT_TYPE, HeaderMap, StatusCode},
+    Json,
+};
 use axum_extra::extract::CookieJar;
 use publish::*;
 use std::sync::Arc;
@@ -10,21 +20,99 @@ use wait_message::*;
 
 pub(crate) async fn connect<AdditionalData, CustomData>(
     State(context): State<Arc<LongPollingServiceContext<AdditionalData, CustomData>>>,
+    headers: HeaderMap,
     jar: CookieJar,
-    Json(messages): Json<Vec<Message>>,
-) -> HandlerResult<Json<Vec<Message>>> {
+    body: Bytes,
+) -> HandlerResult<Json<Vec<Message>>>
+where
+    AdditionalData: Send + Sync + 'static,
+    CustomData: Send + Sync + 'static,
+{
+    if !is_json_content_type(&headers) {
+        return Err(StatusCode::UNSUPPORTED_MEDIA_TYPE.into());
+    }
+
+    let binding = context.request_binding(&jar, &headers);
+
+    let messages = match Batch::parse(&body, context.consts.max_batch_length)
+        .map_err(|_| StatusCode::BAD_REQUEST)?
+    {
+        Batch::Messages(messages) => messages,
+        Batch::TooLarge(messages) => {
+            report_batch_too_large(&context, binding, &messages).await;
+            return Err(Message::batch_too_large().into());
+        }
+    };
+
     tracing::debug!("Got connect request: `{messages:?}`.");
 
+    // Flush suppressed messages of server sends in time for waiting clients.
+    context.arm_throttle_timer();
+
     let ret = match <[_; 1]>::try_from(messages) {
         Ok([message]) => {
             if message.channel.as_deref() == Some("/meta/connect") {
-                wait_client_message_handle(&context, jar, message).await?
+                wait_client_message_handle(&context, binding, message).await?
             } else {
-                publish_handle(&context, jar, vec![message]).await?
+                publish_handle(&context, binding, vec![message]).await?
             }
         }
-        Err(messages) => publish_handle(&context, jar, messages).await?,
+        Err(messages) => publish_handle(&context, binding, messages).await?,
     };
 
     Ok(Json(ret))
 }
+
+/// Check `Content-Type` like [`Json`] extractor does: `application/json` or `application/*+json`.
+#[inline]
+fn is_json_content_type(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+    else {
+        return false;
+    };
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    essence
+        .strip_prefix("application/")
+        .is_some_and(|subtype| subtype == "json" || subtype.ends_with("+json"))
+}
+
+/// Too big batch is one rate limit violation of its sender.
+#[inline]
+async fn report_batch_too_large<AdditionalData, CustomData>(
+    context: &Arc<LongPollingServiceContext<AdditionalData, CustomData>>,
+    binding: Option<RequestBinding>,
+    messages: &[Message],
+) where
+    AdditionalData: Send + Sync + 'static,
+    CustomData: Send + Sync + 'static,
+{
+    let Some(binding) = binding else {
+        return;
+    };
+    let Some(message) = messages.iter().find(|message| message.client_id.is_some()) else {
+        return;
+    };
+    let Some(client_id) = message.client_id else {
+        return;
+    };
+
+    if context
+        .check_client(
+            binding,
+            &client_id,
+            Message::ext_token(message.ext.as_ref()),
+        )
+        .await
+        .is_some()
+    {
+        context.report_rate_limit_violation(client_id).await;
+    }
+}
@@ -1,21 +1,32 @@
 use crate::{
     messages::{Advice, Message},
-    CheckExt as _, CookieJarExt as _, HandlerResult, LongPollingServiceContext, SendError,
+    types::{ClientId, CookieId, RequestBinding},
+    CheckExt as _, HandlerResult, LongPollingServiceContext, SendError,
 };
 use axum::http::StatusCode;
-use axum_extra::extract::CookieJar;
+use serde_json::value::RawValue;
+use std::sync::Arc;
 
 #[inline]
 pub(super) async fn publish_handle<AdditionalData, CustomData>(
-    context: &LongPollingServiceContext<AdditionalData, CustomData>,
-    jar: CookieJar,
+    context: &Arc<LongPollingServiceContext<AdditionalData, CustomData>>,
+    binding: Option<RequestBinding>,
     mut messages: Vec<Message>,
-) -> HandlerResult<Vec<Message>> {
+) -> HandlerResult<Vec<Message>>
+where
+    AdditionalData: Send + Sync + 'static,
+    CustomData: Send + Sync + 'static,
+{
     is_contains_meta_channel(&messages).check(&false, StatusCode::BAD_REQUEST)?;
 
-    let cookie_id = jar
-        .get_cookie_id()
-        .ok_or_else(|| Message::session_unknown(None, None, None))?;
+    let binding = binding.ok_or_else(|| Message::session_unknown(None, None, None))?;
+    let max_message_size = [
+        context.consts.max_message_size,
+        context.rate_limiter.max_message_size(),
+    ]
+    .into_iter()
+    .flatten()
+    .min();
 
     for message in &mut messages {
         let Message {
@@ -23,6 +34,7 @@ pub(super) async fn publish_handle<AdditionalData, CustomData>(
             channel,
             data,
             client_id,
+            ext,
             ..
         } = core::mem::take(message);
 
@@ -30,31 +42,22 @@ pub(super) async fn publish_handle<AdditionalData, CustomData>(
             (None, _) => Message::channel_missing(id),
             (channel, None) => Message::session_unknown(id, channel, Some(Advice::handshake())),
             (Some(channel), Some(client_id)) => {
-                if context.check_client(cookie_id, &client_id).await.is_some() {
-                    match context.send(&channel, data.unwrap_or_default()).await {
-                        Ok(()) => {}
-                        Err(SendError::Closed) => {
-                            tracing::error!(
-                                client_id = %client_id,
-                                channel = channel,
-                                "Channel was closed!"
-                            );
-                        }
-                        Err(SendError::ClientWasntFound(_)) => unreachable!(
-                            "LongPollingServiceContext::send shouldn't return ClientWasntFound"
-                        ),
-                        Err(SendError::InvalidChannel) => {
-                            tracing::error!(
-                                client_id = %client_id,
-                                channel = channel,
-                                "Invalid channel: `{channel}`!"
-                            );
+                match context
+                    .check_client(binding, &client_id, Message::ext_token(ext.as_ref()))
+                    .await
+                {
+                    Some(cookie_id) => {
+                        let size = data.as_ref().map_or(0, |data| data.get().len());
+
+                        if max_message_size.is_some_and(|max| size > max) {
+                            Message::message_too_large(id, Some(channel))
+                        } else {
+                            let data = data_or_null(data);
+                            publish_checked(context, client_id, cookie_id, id, channel, data, size)
+                                .await
                         }
                     }
-
-                    Message::ok(id, Some(channel))
-                } else {
-                    Message::session_unknown(id, Some(channel), None)
+                    None => Message::session_unknown(id, Some(channel), None),
                 }
             }
         };
@@ -63,12 +66,83 @@ pub(super) async fn publish_handle<AdditionalData, CustomData>(
     Ok(messages)
 }
 
+/// Validate payload and rate limit of message and publish it.
+#[inline]
+async fn publish_checked<AdditionalData, CustomData>(
+    context: &Arc<LongPollingServiceContext<AdditionalData, CustomData>>,
+    client_id: ClientId,
+    cookie_id: CookieId,
+    id: Option<String>,
+    channel: String,
+    data: Arc<RawValue>,
+    size: usize,
+) -> Message
+where
+    AdditionalData: Send + Sync + 'static,
+    CustomData: Send + Sync + 'static,
+{
+    if let Err(error) = context.validate_payload(&channel, &data) {
+        Message::invalid_payload(id, Some(channel), &error)
+    } else if context.check_rate_limit(client_id, cookie_id, size).await {
+        publish(context, &client_id, &channel, data).await;
+        Message::ok(id, Some(channel))
+    } else {
+        Message::rate_limit_exceeded(id, Some(channel))
+    }
+}
+
+/// Message without `data` is published with `null` data.
+#[inline]
+fn data_or_null(data: Option<Arc<RawValue>>) -> Arc<RawValue> {
+    data.unwrap_or_else(|| Arc::from(RawValue::NULL.to_owned()))
+}
+
+#[inline]
+async fn publish<AdditionalData, CustomData>(
+    context: &LongPollingServiceContext<AdditionalData, CustomData>,
+    client_id: &ClientId,
+    channel: &str,
+    data: Arc<RawValue>,
+) where
+    AdditionalData: Send + Sync + 'static,
+    CustomData: Send + Sync + 'static,
+{
+    match context.send_raw(channel, data).await {
+        Ok(_) => {}
+        Err(SendError::Closed) => {
+            tracing::error!(
+                client_id = %client_id,
+                channel = channel,
+                "Channel was closed!"
+            );
+        }
+        Err(SendError::ClientWasntFound(_)) => {
+            unreachable!("LongPollingServiceContext::send shouldn't return ClientWasntFound")
+        }
+        Err(SendError::InvalidMessage) => {
+            unreachable!("LongPollingServiceContext::send_raw shouldn't return InvalidMessage")
+        }
+        Err(SendError::Full) => {
+            unreachable!("LongPollingServiceContext::send_raw shouldn't return Full")
+        }
+        Err(SendError::InvalidChannel) => {
+            tracing::error!(
+                client_id = %client_id,
+                channel = channel,
+                "Invalid channel: `{channel}`!"
+            );
+        }
+    }
+
+    context.arm_throttle_timer();
+}
+
 #[inline]
 fn is_contains_meta_channel(messages: &[Message]) -> bool {
     messages.iter().any(|message| {
         message
             .channel
             .as_ref()
-            .map_or(false, |channel| channel.contains("/meta/"))
+            .is_some_and(|channel| channel.contains("/meta/"))
     })
 }
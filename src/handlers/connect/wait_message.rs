@@ -1,18 +1,15 @@
 use crate::{
     error::HandlerResult,
     messages::{Advice, Message, SubscriptionMessage},
-    types::ClientReceiverError,
-    CookieJarExt as _, LongPollingServiceContext,
+    types::{ClientReceiverError, CloseReason, RequestBinding},
+    LongPollingServiceContext, ReconnectAdvice,
 };
-use axum::http::StatusCode;
-use axum_extra::extract::CookieJar;
 use core::time::Duration;
-use serde_json::json;
 
 #[inline]
 pub(super) async fn wait_client_message_handle<AdditionalData, CustomData>(
     context: &LongPollingServiceContext<AdditionalData, CustomData>,
-    jar: CookieJar,
+    binding: Option<RequestBinding>,
     message: Message,
 ) -> HandlerResult<Vec<Message>> {
     let Message {
@@ -20,17 +17,49 @@ pub(super) async fn wait_client_message_handle<AdditionalData, CustomData>(
         channel,
         advice,
         client_id,
+        ext,
         ..
     } = message;
     let session_unknown =
         || Message::session_unknown(id.clone(), channel.clone(), Some(Advice::handshake()));
 
-    let cookie_id = jar.get_cookie_id().ok_or_else(session_unknown)?;
+    let binding = binding.ok_or_else(session_unknown)?;
     let client_id = client_id.ok_or_else(session_unknown)?;
-    context
-        .check_client(cookie_id, &client_id)
+    let Some(cookie_id) = context
+        .check_client(binding, &client_id, Message::ext_token(ext.as_ref()))
         .await
-        .ok_or_else(session_unknown)?;
+    else {
+        let message =
+            context
+                .evicted_clients
+                .get(&client_id)
+                .map_or_else(&session_unknown, |advice| Message {
+                    advice: Some(advice.into()),
+                    ..Message::ok(id.clone(), channel.clone())
+                });
+        return Err(message.into());
+    };
+
+    let _long_poll_guard = match context.consts.max_sessions_per_browser {
+        Some(max) => match context.long_polls_counter.acquire(cookie_id, max) {
+            Some(guard) => Some(guard),
+            None => {
+                tracing::info!(
+                    client_id = %client_id,
+                    "Browser of client `{client_id}` exceeded `{max}` pending connects."
+                );
+
+                return Ok(vec![Message {
+                    advice: Some(Advice::multiple_clients(
+                        context.consts.timeout,
+                        context.consts.multiple_clients_interval,
+                    )),
+                    ..Message::ok(id, channel)
+                }]);
+            }
+        },
+        None => None,
+    };
 
     let timeout = advice
         .and_then(|advice| advice.timeout)
@@ -44,18 +73,15 @@ pub(super) async fn wait_client_message_handle<AdditionalData, CustomData>(
     let SubscriptionMessage {
         channel: recv_channel,
         msg,
-    } = rx
-        .recv_timeout(timeout)
-        .await
-        .map_err(|error| {
-            client_receiver_error_to_message(&error, id.clone(), channel.clone(), context)
-        })?
-        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+        ..
+    } = rx.recv_timeout(timeout).await.map_err(|error| {
+        client_receiver_error_to_message(&error, id.clone(), channel.clone(), context)
+    })?;
 
     Ok(vec![
         Message {
-            channel: Some(recv_channel),
-            data: Some(json!(msg)),
+            channel: Some(recv_channel.to_string()),
+            data: Some(msg),
             ..Default::default()
         },
         Message::ok(id, channel),
@@ -84,5 +110,16 @@ fn client_receiver_error_to_message<AdditionalData, CustomData>(
             error: Some("Two connection with same client_id.".to_owned()),
             ..Default::default()
         },
+        ClientReceiverError::Closed(CloseReason::Evicted(advice)) => Message {
+            advice: Some(advice.into()),
+            ..Message::ok(id, channel)
+        },
+        ClientReceiverError::Closed(CloseReason::Disconnected) => Message {
+            advice: Some(ReconnectAdvice::None.into()),
+            ..Message::ok(id, channel)
+        },
+        ClientReceiverError::Closed(CloseReason::Removed) => {
+            Message::session_unknown(id, channel, Some(Advice::handshake()))
+        }
     }
 }
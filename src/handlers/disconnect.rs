@@ -1,13 +1,14 @@
 use crate::{
-    error::HandlerResult, messages::Message, CheckExt as _, CookieJarExt as _,
+    error::HandlerResult, messages::Message, types::CloseReason, CheckExt as _,
     LongPollingServiceContext, ZERO_CLIENT_ID,
 };
-use axum::{extract::State, Json};
+use axum::{extract::State, http::HeaderMap, Json};
 use axum_extra::extract::CookieJar;
 use std::sync::Arc;
 
 pub(crate) async fn disconnect<AdditionalData, CustomData>(
     State(context): State<Arc<LongPollingServiceContext<AdditionalData, CustomData>>>,
+    headers: HeaderMap,
     jar: CookieJar,
     Json([message]): Json<[Message; 1]>,
 ) -> HandlerResult<Json<[Message; 1]>> {
@@ -22,6 +23,7 @@ pub(crate) async fn disconnect<AdditionalData, CustomData>(
         id,
         channel,
         client_id,
+        ext,
         ..
     } = message;
 
@@ -29,13 +31,18 @@ pub(crate) async fn disconnect<AdditionalData, CustomData>(
 
     channel.check_or("/meta/disconnect", session_unknown)?;
 
-    let cookie_id = jar.get_cookie_id().ok_or_else(session_unknown)?;
+    let binding = context
+        .request_binding(&jar, &headers)
+        .ok_or_else(session_unknown)?;
     let client_id = client_id.ok_or_else(session_unknown)?;
     context
-        .check_client(cookie_id, &client_id)
+        .check_client(binding, &client_id, Message::ext_token(ext.as_ref()))
         .await
         .ok_or_else(session_unknown)?;
 
+    context
+        .close_client(&client_id, CloseReason::Disconnected)
+        .await;
     context.unsubscribe(client_id).await;
 
     Ok(Json([Message::ok(id, channel)]))
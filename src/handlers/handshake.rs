@@ -1,11 +1,12 @@
 use crate::{
     error::HandlerResult,
     messages::{Advice, Message},
-    types::{CookieId, Event, BAYEUX_BROWSER},
-    CheckExt, LongPollingServiceContext,
+    types::{CookieId, Event, RequestBinding},
+    CheckExt, LongPollingServiceContext, SessionAttributes, SessionBinding,
 };
 use axum::{extract::State, http::HeaderMap, Extension, Json};
-use axum_extra::extract::cookie::{Cookie, CookieJar};
+use axum_extra::extract::cookie::CookieJar;
+use serde_json::json;
 use std::sync::Arc;
 
 pub(crate) async fn handshake<AdditionalData, CustomData>(
@@ -16,7 +17,7 @@ pub(crate) async fn handshake<AdditionalData, CustomData>(
     Json([message]): Json<[Message; 1]>,
 ) -> HandlerResult<(CookieJar, Json<[Message; 1]>)>
 where
-    AdditionalData: Send + Sync + 'static,
+    AdditionalData: Clone + Send + Sync + 'static,
     CustomData: Send + Sync + 'static,
 {
     tracing::info!(
@@ -29,6 +30,7 @@ where
         channel,
         id,
         minimum_version,
+        ext,
         ..
     } = message;
 
@@ -39,23 +41,30 @@ where
         Message::wrong_minimum_version(id.clone(), minimum_version.clone())
     })?;
 
-    #[allow(clippy::option_if_let_else)]
-    let cookie_id = if let Some(cookie_id) = jar
-        .get(BAYEUX_BROWSER)
-        .map(Cookie::value)
-        .map(CookieId::parse)
-        .and_then(Result::ok)
-    {
-        cookie_id
-    } else {
-        let cookie_id = CookieId::gen();
-        jar = jar.add(Cookie::new(BAYEUX_BROWSER, cookie_id.to_string()));
-        cookie_id
+    let cookie_id = match context.request_binding(&jar, &headers) {
+        Some(RequestBinding::Browser(cookie_id)) => cookie_id,
+        Some(RequestBinding::ClientId) => CookieId::gen(&*context.id_generator),
+        None if matches!(context.session_binding, SessionBinding::Cookie) => {
+            let cookie_id = CookieId::gen(&*context.id_generator);
+            jar = jar.add(context.cookie_config.build_cookie(cookie_id));
+            cookie_id
+        }
+        None => return Err(Message::session_unknown(id, channel, None).into()),
     };
 
-    let client_id = context.register(cookie_id).await.ok_or_else(|| {
-        Message::session_unknown(id.clone(), channel.clone(), Some(Advice::handshake()))
-    })?;
+    let attributes = Arc::new(SessionAttributes::default());
+    attributes.insert(data.clone());
+    let user = context
+        .user_identity
+        .as_ref()
+        .and_then(|user_identity| user_identity.extract(&headers, ext.as_ref(), &attributes));
+
+    let client_id = context
+        .register(cookie_id, attributes, user, false)
+        .await
+        .ok_or_else(|| {
+            Message::session_unknown(id.clone(), channel.clone(), Some(Advice::handshake()))
+        })?;
 
     let _ = context
         .tx
@@ -77,6 +86,10 @@ where
         jar,
         Json([Message {
             client_id: Some(client_id),
+            ext: context
+                .session_binding
+                .sign(&client_id)
+                .map(|token| json!({ "token": token })),
             version: Some("1.0".into()),
             supported_connection_types: Some(vec!["long-polling".into()]),
             advice: Some(Advice::retry(
@@ -1,6 +1,8 @@
 use crate::{
-    error::HandlerResult, messages::Message, types::Event, CheckExt, CookieJarExt,
-    LongPollingServiceContext, ZERO_CLIENT_ID,
+    error::{HandlerError, HandlerResult, SubscribeError},
+    messages::Message,
+    types::Event,
+    CheckExt, LongPollingServiceContext, ZERO_CLIENT_ID,
 };
 use axum::{
     extract::State,
@@ -33,6 +35,7 @@ where
         channel,
         subscription,
         client_id,
+        ext,
         ..
     } = message;
 
@@ -40,10 +43,12 @@ where
 
     channel.check_or("/meta/subscribe", session_unknown)?;
 
-    let cookie_id = jar.get_cookie_id().ok_or_else(session_unknown)?;
+    let binding = context
+        .request_binding(&jar, &headers)
+        .ok_or_else(session_unknown)?;
     let client_id = client_id.ok_or_else(session_unknown)?;
-    context
-        .check_client(cookie_id, &client_id)
+    let cookie_id = context
+        .check_client(binding, &client_id, Message::ext_token(ext.as_ref()))
         .await
         .ok_or_else(session_unknown)?;
 
@@ -52,6 +57,17 @@ where
         .is_empty()
         .check_or(&false, || Message::subscription_missing(id.clone()))?;
 
+    context
+        .check_rate_limit(
+            client_id,
+            cookie_id,
+            subscription.iter().map(String::len).sum(),
+        )
+        .await
+        .check_or(&true, || {
+            Message::rate_limit_exceeded(id.clone(), channel.clone())
+        })?;
+
     subscription.iter().try_for_each(|name| {
         context
             .channel_name_validator
@@ -59,7 +75,19 @@ where
             .check(&true, StatusCode::BAD_REQUEST)
     })?;
 
-    context.subscribe(client_id, &subscription).await;
+    context
+        .subscribe(client_id, &subscription)
+        .await
+        .map_err(|error| -> HandlerError {
+            match error {
+                SubscribeError::ClientWasntFound(_) => session_unknown().into(),
+                SubscribeError::InvalidChannel => StatusCode::BAD_REQUEST.into(),
+                SubscribeError::TooManySubscriptions => {
+                    Message::too_many_subscriptions(id.clone()).into()
+                }
+                SubscribeError::TooManyChannels => Message::too_many_channels(id.clone()).into(),
+            }
+        })?;
 
     let _ = context
         .tx
@@ -84,16 +84,25 @@
 //! If `clientId` will be used with different `BAYEUX_BROWSER` cookie,
 //! server will return '402::session_unknown' error.
 //!
+//! Clients without cookie jar can be bound through custom header or `clientId` only, see
+//! [`LongPollingServiceContextBuilder::session_binding`].
+//!
 //! # How get server events
 //!
-//! Server have 3 events:
+//! Server have 8 events:
 //! 1) [`Event::SessionAdded`]
 //! 2) [`Event::Subscribe`]
-//! 3) [`Event::SessionRemoved`]
-//! 4) [`Event::CustomData`]
+//! 3) [`Event::ServerSubscribe`]
+//! 4) [`Event::ServerUnsubscribe`]
+//! 5) [`Event::SessionEvicted`]
+//! 6) [`Event::MessagesSuppressed`]
+//! 7) [`Event::SessionRemoved`]
+//! 8) [`Event::CustomData`]
 //!
 //! `SessionAdded` and `Subscribe` can contain additional data, which will be attached through
 //! [`axum::Extension`].
+//! Handshake additional data is also stored in session attributes, which can be read through
+//! [`LongPollingServiceContext::session`] and are included in [`Event::SessionRemoved`].
 //! To get those events, you must use get receive channel [`LongPollingServiceContext::rx`].
 //! Server do not use [`Event::CustomData`], it user custom message which can be received in
 //! receiver.
@@ -149,9 +158,26 @@
 //!         } => {
 //!             println!("subscribed on channels({channels:?}) with clientId({client_id}), headers({headers:?}), data({data:?})");
 //!         }
+//!         Event::ServerSubscribe{
+//!             client_id,
+//!             ref channels,
+//!         } => println!("server subscribed clientId({client_id}) on channels({channels:?})"),
+//!         Event::ServerUnsubscribe{
+//!             client_id,
+//!             ref channels,
+//!         } => println!("server unsubscribed clientId({client_id}) from channels({channels:?})"),
+//!         Event::SessionEvicted{
+//!             client_id,
+//!             advice,
+//!         } => println!("clientId({client_id}) session evicted with advice({advice:?})"),
+//!         Event::MessagesSuppressed{
+//!             ref channel,
+//!             count,
+//!         } => println!("{count} messages of channel({channel}) were suppressed by throttle"),
 //!         Event::SessionRemoved{
 //!             client_id,
-//!         } => println!("clientId({client_id}) session removed"),
+//!             ref attributes,
+//!         } => println!("clientId({client_id}) session removed, data({:?})", attributes.get::<ContextData>()),
 //!         Event::CustomData(msg) => println!("got CustomData({msg})"),
 //!     }
 //! }
@@ -165,5 +191,6 @@ mod handlers;
 mod types;
 mod utils;
 
+pub use axum_extra::extract::cookie::SameSite;
 pub(crate) use ext::*;
 pub use {context::*, types::error::*, types::*};
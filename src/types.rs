@@ -1,10 +1,27 @@
 mod channel;
+mod channel_name_validator;
 mod client_id;
+mod client_queue;
 mod client_receiver;
 mod client_sender;
+mod conflation;
+mod cookie_config;
 mod cookie_id;
+mod delivery_report;
 mod id;
+mod id_generator;
+mod payload_validator;
+mod priority;
+mod publisher;
+mod rate_limit;
+mod reconnect_advice;
+mod scheduled_message;
+mod session_attributes;
+mod session_binding;
 mod subscription_id;
+mod throttle;
+mod typed_channel;
+mod user_identity;
 
 /// Contains errors.
 pub mod error;
@@ -12,5 +29,12 @@ mod events;
 /// Contains cometd Message struct.
 pub mod messages;
 
-pub use {channel::*, client_id::*, events::*};
-pub(crate) use {client_receiver::*, client_sender::*, cookie_id::*, id::*, subscription_id::*};
+pub use {
+    channel::*, channel_name_validator::*, client_id::*, delivery_report::*, events::*,
+    id_generator::*, priority::*, publisher::*, rate_limit::*, reconnect_advice::*,
+    scheduled_message::*, session_attributes::*, session_binding::*, throttle::*, typed_channel::*,
+};
+pub(crate) use {
+    client_queue::*, client_receiver::*, client_sender::*, conflation::*, cookie_config::*,
+    cookie_id::*, id::*, payload_validator::*, subscription_id::*, user_identity::*,
+};
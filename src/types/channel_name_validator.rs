@@ -0,0 +1,190 @@
+use core::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// Validate channel names of subscriptions and sent messages.
+///
+/// Set with [`LongPollingServiceContextBuilder::channel_name_validator`].
+///
+/// [`LongPollingServiceContextBuilder::channel_name_validator`]: crate::LongPollingServiceContextBuilder::channel_name_validator
+pub trait ChannelNameValidator: Debug + Send + Sync {
+    /// Return `true` if client can subscribe to `name`. `name` may contain wildcards.
+    fn validate_subscribe_channel_name(&self, name: &str) -> bool;
+
+    /// Return `true` if messages can be sent to `name`.
+    fn validate_send_channel_name(&self, name: &str) -> bool;
+}
+
+/// Configurable [`ChannelNameValidator`].
+///
+/// Channel name is a list of `/`-prefixed non-empty segments with optional trailing `/`.
+/// Subscriptions may also end with `/*` or `/**` wildcard.
+/// By default segment may contain only `[a-zA-Z0-9_\-!~()$@]` characters.
+///
+/// # Example
+/// ```rust,no_run
+/// use axum_cometd::{ChannelNameRules, LongPollingServiceContextBuilder};
+///
+/// let context = LongPollingServiceContextBuilder::new()
+///     .channel_name_validator(
+///         ChannelNameRules::new()
+///             .allowed_char(|c| c.is_alphanumeric() || matches!(c, '.' | '_' | '-'))
+///             .max_depth(4)
+///             .max_length(128)
+///             .reserved_prefix("/service/"),
+///     )
+///     .build::<(), ()>();
+/// ```
+#[derive(Clone)]
+pub struct ChannelNameRules {
+    allowed_char: Option<Arc<dyn Fn(char) -> bool + Send + Sync>>,
+    max_depth: Option<usize>,
+    max_length: Option<usize>,
+    reserved_prefixes: Vec<String>,
+    wildcards: bool,
+}
+
+impl Debug for ChannelNameRules {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ChannelNameRules")
+            .field("max_depth", &self.max_depth)
+            .field("max_length", &self.max_length)
+            .field("reserved_prefixes", &self.reserved_prefixes)
+            .field("wildcards", &self.wildcards)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for ChannelNameRules {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChannelNameRules {
+    /// Construct a new `ChannelNameRules` with default rules.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            allowed_char: None,
+            max_depth: None,
+            max_length: None,
+            reserved_prefixes: Vec::new(),
+            wildcards: true,
+        }
+    }
+
+    /// Set predicate for characters allowed in channel name segments.
+    #[inline(always)]
+    #[must_use]
+    pub fn allowed_char(
+        mut self,
+        allowed_char: impl Fn(char) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.allowed_char = Some(Arc::new(allowed_char));
+        self
+    }
+
+    /// Set maximum number of segments in channel name, not counting wildcard.
+    #[inline(always)]
+    #[must_use]
+    pub const fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Set maximum channel name length in bytes.
+    #[inline(always)]
+    #[must_use]
+    pub const fn max_length(mut self, length: usize) -> Self {
+        self.max_length = Some(length);
+        self
+    }
+
+    /// Reject channel names, which start with `prefix`,
+    /// and wildcard subscriptions, which match such channels (e.g. `/**`).
+    #[inline(always)]
+    #[must_use]
+    pub fn reserved_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.reserved_prefixes.push(prefix.into());
+        self
+    }
+
+    /// Allow or forbid wildcard subscriptions. Allowed by default.
+    #[inline(always)]
+    #[must_use]
+    pub const fn wildcards(mut self, allowed: bool) -> Self {
+        self.wildcards = allowed;
+        self
+    }
+
+    fn validate(&self, name: &str, allow_wildcards: bool) -> bool {
+        if self.max_length.is_some_and(|max| name.len() > max)
+            || self
+                .reserved_prefixes
+                .iter()
+                .any(|prefix| name.starts_with(prefix.as_str()) || covers(name, prefix))
+        {
+            return false;
+        }
+
+        let Some(name) = name.strip_prefix('/') else {
+            return false;
+        };
+
+        let mut depth = 0_usize;
+        let mut segments = name.split('/').peekable();
+        while let Some(segment) = segments.next() {
+            let is_last = segments.peek().is_none();
+
+            match segment {
+                "" if is_last => {}
+                "*" | "**" if is_last && allow_wildcards => {}
+                segment if !segment.is_empty() && segment.chars().all(|c| self.is_allowed(c)) => {
+                    depth += 1;
+                }
+                _ => return false,
+            }
+        }
+
+        self.max_depth.is_none_or(|max| depth <= max)
+    }
+
+    #[inline]
+    fn is_allowed(&self, c: char) -> bool {
+        self.allowed_char.as_ref().map_or_else(
+            || is_default_allowed_char(c),
+            |allowed_char| allowed_char(c),
+        )
+    }
+}
+
+/// Return `true` if wildcard `name` matches some channels, which start with `prefix`.
+#[inline]
+fn covers(name: &str, prefix: &str) -> bool {
+    match (name.strip_suffix("**"), name.strip_suffix('*')) {
+        (Some(base), _) => prefix.starts_with(base),
+        // Single segment wildcard matches prefix only if rest of prefix fits into one segment.
+        (None, Some(base)) => prefix
+            .strip_prefix(base)
+            .is_some_and(|rest| !rest.contains('/')),
+        (None, None) => false,
+    }
+}
+
+impl ChannelNameValidator for ChannelNameRules {
+    #[inline]
+    fn validate_subscribe_channel_name(&self, name: &str) -> bool {
+        self.validate(name, self.wildcards)
+    }
+
+    #[inline]
+    fn validate_send_channel_name(&self, name: &str) -> bool {
+        name != "/" && self.validate(name, false)
+    }
+}
+
+#[inline]
+const fn is_default_allowed_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '!' | '~' | '(' | ')' | '$' | '@')
+}
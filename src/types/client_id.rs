@@ -1,4 +1,7 @@
-use crate::types::{Id, ZERO_ID};
+use crate::{
+    types::{Id, ZERO_ID},
+    IdGenerator,
+};
 use core::fmt::{Debug, Display, Formatter};
 use serde::{Deserialize, Serialize};
 
@@ -10,8 +13,8 @@ pub struct ClientId(Id);
 
 impl ClientId {
     #[inline(always)]
-    pub(crate) fn gen() -> Self {
-        Self(Id::gen())
+    pub(crate) fn gen(generator: &dyn IdGenerator) -> Self {
+        Self(Id::gen(generator))
     }
 }
 
@@ -0,0 +1,199 @@
+use crate::{messages::SubscriptionMessage, Priority};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+use tokio::{
+    sync::{mpsc::error::TrySendError, Notify},
+    time::Instant,
+};
+
+/// State shared between both sides of client queue.
+#[derive(Debug)]
+struct Shared {
+    lanes: Mutex<Lanes>,
+    notify: Notify,
+    senders: AtomicUsize,
+    capacity: usize,
+}
+
+/// Pending messages of each priority.
+#[derive(Debug, Default)]
+struct Lanes {
+    normal: VecDeque<SubscriptionMessage>,
+    urgent: VecDeque<SubscriptionMessage>,
+    /// Receiver or all senders were dropped.
+    closed: bool,
+}
+
+impl Shared {
+    #[inline(always)]
+    fn lock(&self) -> MutexGuard<'_, Lanes> {
+        self.lanes.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[inline]
+    fn close(&self) {
+        self.lock().closed = true;
+        self.notify.notify_one();
+    }
+}
+
+impl Lanes {
+    #[inline(always)]
+    const fn lane(&mut self, priority: Priority) -> &mut VecDeque<SubscriptionMessage> {
+        match priority {
+            Priority::Normal => &mut self.normal,
+            Priority::Urgent => &mut self.urgent,
+        }
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.normal.len() + self.urgent.len()
+    }
+
+    /// Drop expired messages of both lanes.
+    #[inline]
+    fn retain_not_expired(&mut self, now: Instant) {
+        self.normal.retain(|pending| !is_expired(pending, now));
+        self.urgent.retain(|pending| !is_expired(pending, now));
+    }
+
+    #[inline(always)]
+    fn pop(&mut self) -> Option<SubscriptionMessage> {
+        self.urgent.pop_front().or_else(|| self.normal.pop_front())
+    }
+}
+
+/// Sending side of client queue. Message goes to lane of its priority.
+///
+/// Message with conflation key replaces pending message of the same lane with the same key,
+/// so conflated channels never fill the queue. Expired messages are dropped from full queue.
+#[derive(Debug)]
+pub(crate) struct ClientQueueSender {
+    shared: Arc<Shared>,
+}
+
+impl ClientQueueSender {
+    #[inline]
+    pub(crate) fn try_send(
+        &self,
+        msg: SubscriptionMessage,
+    ) -> Result<(), TrySendError<SubscriptionMessage>> {
+        let mut lanes = self.shared.lock();
+        if lanes.closed {
+            return Err(TrySendError::Closed(msg));
+        }
+
+        if let Some(ref key) = msg.conflation_key {
+            lanes
+                .lane(msg.priority)
+                .retain(|pending| pending.conflation_key.as_ref() != Some(key));
+        }
+        if lanes.len() >= self.shared.capacity {
+            lanes.retain_not_expired(Instant::now());
+        }
+        if lanes.len() >= self.shared.capacity {
+            return Err(TrySendError::Full(msg));
+        }
+        lanes.lane(msg.priority).push_back(msg);
+        drop(lanes);
+
+        self.shared.notify.notify_one();
+
+        Ok(())
+    }
+}
+
+impl Clone for ClientQueueSender {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::Relaxed);
+
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl Drop for ClientQueueSender {
+    #[inline]
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.shared.close();
+        }
+    }
+}
+
+/// Receiving side of client queue.
+///
+/// Urgent messages are received before normal ones.
+/// Messages, which are still pending after all senders were dropped, are received
+/// before queue reports closing.
+#[derive(Debug)]
+pub(crate) struct ClientQueue {
+    shared: Arc<Shared>,
+}
+
+impl ClientQueue {
+    /// Create queue, which holds at most `capacity` messages of all priorities.
+    #[inline]
+    pub(crate) fn channel(capacity: usize) -> (ClientQueueSender, Self) {
+        let shared = Arc::new(Shared {
+            lanes: Mutex::default(),
+            notify: Notify::new(),
+            senders: AtomicUsize::new(1),
+            capacity,
+        });
+
+        (
+            ClientQueueSender {
+                shared: Arc::clone(&shared),
+            },
+            Self { shared },
+        )
+    }
+
+    /// Receive next not expired message. Cancel safe.
+    #[inline]
+    pub(crate) async fn recv(&mut self) -> Option<SubscriptionMessage> {
+        loop {
+            let (msg, closed) = {
+                let mut lanes = self.shared.lock();
+                (lanes.pop(), lanes.closed)
+            };
+
+            let Some(msg) = msg else {
+                if closed {
+                    return None;
+                }
+                self.shared.notify.notified().await;
+                continue;
+            };
+
+            if !is_expired(&msg, Instant::now()) {
+                return Some(msg);
+            }
+
+            tracing::debug!(
+                channel = &*msg.channel,
+                "Expired message `{}` was dropped.",
+                msg.msg
+            );
+        }
+    }
+}
+
+impl Drop for ClientQueue {
+    #[inline]
+    fn drop(&mut self) {
+        self.shared.close();
+    }
+}
+
+#[inline(always)]
+fn is_expired(msg: &SubscriptionMessage, now: Instant) -> bool {
+    msg.expires_at.is_some_and(|expires_at| expires_at <= now)
+}
@@ -1,11 +1,15 @@
 // https://github.com/rust-lang/rust-clippy/issues/10198
 #![allow(clippy::std_instead_of_core)]
 
-use crate::{messages::SubscriptionMessage, types::Signals};
+use crate::{
+    messages::SubscriptionMessage,
+    types::{ClientQueue, CloseReason, Signals},
+};
 use core::{fmt::Debug, time::Duration};
 use std::sync::Arc;
 use tokio::{
-    sync::{mpsc::Receiver, Mutex, TryLockError},
+    pin, select,
+    sync::{Mutex, TryLockError},
     time,
 };
 
@@ -15,21 +19,20 @@ pub(crate) enum ClientReceiverError {
     Elapsed(#[from] time::error::Elapsed),
     #[error("double lock")]
     AlreadyLocked(#[from] TryLockError),
+    #[error("closed: {0:?}")]
+    Closed(CloseReason),
 }
 
 // TODO: Unite Arc's.
 #[derive(Debug)]
 pub(crate) struct ClientReceiver {
     signals: Arc<Signals>,
-    rx: Arc<Mutex<Receiver<SubscriptionMessage>>>,
+    rx: Arc<Mutex<ClientQueue>>,
 }
 
 impl ClientReceiver {
     #[inline(always)]
-    pub(crate) fn new(
-        signals: Arc<Signals>,
-        rx: Arc<Mutex<Receiver<SubscriptionMessage>>>,
-    ) -> Self {
+    pub(crate) const fn new(signals: Arc<Signals>, rx: Arc<Mutex<ClientQueue>>) -> Self {
         Self { signals, rx }
     }
 
@@ -37,10 +40,46 @@ impl ClientReceiver {
     pub(crate) async fn recv_timeout(
         &mut self,
         duration: Duration,
-    ) -> Result<Option<SubscriptionMessage>, ClientReceiverError> {
+    ) -> Result<SubscriptionMessage, ClientReceiverError> {
+        let mut rx = self.rx.try_lock()?;
+
+        let closed = self.signals.closed.notified();
+        pin!(closed);
+        closed.as_mut().enable();
+
+        if let Some(reason) = self.signals.close_reason() {
+            return Err(ClientReceiverError::Closed(reason));
+        }
+
+        select! {
+            biased;
+            () = closed => Err(self.closed_error()),
+            msg = time::timeout(duration, rx.recv()) => msg?.ok_or_else(|| self.closed_error()),
+        }
+    }
+
+    #[inline]
+    pub(crate) async fn recv(&mut self) -> Result<SubscriptionMessage, ClientReceiverError> {
         let mut rx = self.rx.try_lock()?;
-        let msg = time::timeout(duration, rx.recv()).await?;
-        Ok(msg)
+
+        let closed = self.signals.closed.notified();
+        pin!(closed);
+        closed.as_mut().enable();
+
+        if let Some(reason) = self.signals.close_reason() {
+            return Err(ClientReceiverError::Closed(reason));
+        }
+
+        select! {
+            biased;
+            () = closed => Err(self.closed_error()),
+            msg = rx.recv() => msg.ok_or_else(|| self.closed_error()),
+        }
+    }
+
+    #[inline(always)]
+    fn closed_error(&self) -> ClientReceiverError {
+        ClientReceiverError::Closed(self.signals.close_reason().unwrap_or(CloseReason::Removed))
     }
 }
 
@@ -2,22 +2,25 @@ mod client_timeout;
 
 use crate::{
     messages::SubscriptionMessage,
-    types::{ClientId, ClientReceiver, CookieId},
-    LongPollingServiceContext,
+    types::{ClientId, ClientQueue, ClientQueueSender, ClientReceiver, CookieId},
+    LongPollingServiceContext, ReconnectAdvice, SessionAttributes,
 };
-use core::{fmt::Debug, time::Duration};
-use std::sync::Arc;
-use tokio::sync::{
-    mpsc::{error::SendError, Receiver, Sender},
-    Mutex, Notify,
+use core::{
+    fmt::Debug,
+    sync::atomic::{AtomicUsize, Ordering},
 };
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{mpsc::error::TrySendError, Mutex, Notify};
 
 #[derive(Debug)]
 pub(crate) struct ClientSender {
     cookie_id: CookieId,
+    subscriptions_count: AtomicUsize,
+    attributes: Arc<SessionAttributes>,
+    user: Option<Arc<str>>,
     signals: Arc<Signals>,
-    tx: Sender<SubscriptionMessage>,
-    rx: Arc<Mutex<Receiver<SubscriptionMessage>>>,
+    tx: ClientQueueSender,
+    rx: Arc<Mutex<ClientQueue>>,
 }
 
 #[derive(Debug, Default)]
@@ -25,31 +28,66 @@ pub(crate) struct Signals {
     pub(crate) stop_signal: Notify,
     pub(crate) start_timeout: Notify,
     pub(crate) cancel_timeout: Notify,
+    pub(crate) closed: Notify,
+    close_reason: OnceLock<CloseReason>,
+}
+
+/// Reason why pending `/meta/connect` was woken up without message.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum CloseReason {
+    /// Client was evicted by server with given advice.
+    Evicted(ReconnectAdvice),
+    /// Client sent `/meta/disconnect`.
+    Disconnected,
+    /// Client was removed (e.g. by timeout).
+    Removed,
+}
+
+impl Signals {
+    /// Set close reason (only first one is kept) and wake up pending receiver.
+    #[inline]
+    pub(crate) fn close(&self, reason: CloseReason) {
+        let _ = self.close_reason.set(reason);
+        self.closed.notify_waiters();
+    }
+
+    #[inline(always)]
+    pub(crate) fn close_reason(&self) -> Option<CloseReason> {
+        self.close_reason.get().copied()
+    }
 }
 
 impl ClientSender {
+    /// Create session. `local` session has no timeout: it's removed by its owner.
     #[inline]
     pub(crate) fn create<AdditionalData, CustomData>(
         context: Arc<LongPollingServiceContext<AdditionalData, CustomData>>,
         cookie_id: CookieId,
         client_id: ClientId,
-        timeout: Duration,
-        tx: Sender<SubscriptionMessage>,
-        rx: Receiver<SubscriptionMessage>,
+        attributes: Arc<SessionAttributes>,
+        user: Option<Arc<str>>,
+        local: bool,
     ) -> Self
     where
         AdditionalData: Send + Sync + 'static,
         CustomData: Send + Sync + 'static,
     {
         let signals = Arc::new(Signals::default());
+        let (tx, rx) = ClientQueue::channel(context.consts.client_channel_capacity);
         let rx = Arc::new(Mutex::new(rx));
+        let timeout = context.consts.max_interval;
 
-        client_timeout::spawn(context, client_id, timeout, Arc::clone(&signals));
+        if !local {
+            client_timeout::spawn(context, client_id, timeout, Arc::clone(&signals));
 
-        signals.start_timeout.notify_waiters();
+            signals.start_timeout.notify_waiters();
+        }
 
         Self {
             cookie_id,
+            subscriptions_count: AtomicUsize::new(0),
+            attributes,
+            user,
             signals,
             tx,
             rx,
@@ -61,6 +99,31 @@ impl ClientSender {
         self.cookie_id
     }
 
+    #[inline(always)]
+    pub(crate) const fn attributes(&self) -> &Arc<SessionAttributes> {
+        &self.attributes
+    }
+
+    #[inline(always)]
+    pub(crate) fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    #[inline(always)]
+    pub(crate) fn subscriptions_count(&self) -> usize {
+        self.subscriptions_count.load(Ordering::Acquire)
+    }
+
+    #[inline(always)]
+    pub(crate) fn add_subscriptions(&self, count: usize) {
+        self.subscriptions_count.fetch_add(count, Ordering::AcqRel);
+    }
+
+    #[inline(always)]
+    pub(crate) fn remove_subscriptions(&self, count: usize) {
+        self.subscriptions_count.fetch_sub(count, Ordering::AcqRel);
+    }
+
     #[inline]
     pub(crate) fn subscribe(&self) -> ClientReceiver {
         self.signals.cancel_timeout.notify_waiters();
@@ -68,16 +131,27 @@ impl ClientSender {
     }
 
     #[inline(always)]
-    pub(crate) async fn send(
+    pub(crate) fn close(&self, reason: CloseReason) {
+        self.signals.close(reason);
+    }
+
+    #[inline(always)]
+    pub(crate) fn sender(&self) -> ClientQueueSender {
+        self.tx.clone()
+    }
+
+    #[inline(always)]
+    pub(crate) fn try_send(
         &self,
         msg: SubscriptionMessage,
-    ) -> Result<(), SendError<SubscriptionMessage>> {
-        self.tx.send(msg).await
+    ) -> Result<(), TrySendError<SubscriptionMessage>> {
+        self.tx.try_send(msg)
     }
 }
 
 impl Drop for ClientSender {
     fn drop(&mut self) {
+        self.signals.close(CloseReason::Removed);
         self.signals.stop_signal.notify_one();
     }
 }
@@ -17,6 +17,7 @@ pub(super) fn spawn(
             ref stop_signal,
             ref start_timeout,
             ref cancel_timeout,
+            ..
         } = *signals;
 
         loop {
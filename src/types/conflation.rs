@@ -0,0 +1,43 @@
+use core::fmt::{Debug, Formatter};
+use serde_json::{value::RawValue, Value as JsonValue};
+use std::sync::Arc;
+
+type ConflationKeyFn = dyn Fn(&JsonValue) -> Option<String> + Send + Sync;
+
+/// Conflation rule of channel pattern.
+/// Pending messages with the same key are replaced with the newest one in the client queue.
+pub(crate) struct Conflation(Option<Box<ConflationKeyFn>>);
+
+impl Conflation {
+    /// Conflate all messages of channel.
+    #[inline(always)]
+    pub(crate) const fn channel() -> Self {
+        Self(None)
+    }
+
+    /// Conflate messages of channel with the same key extracted from message data.
+    #[inline(always)]
+    pub(crate) fn by_key(
+        key: impl Fn(&JsonValue) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Some(Box::new(key)))
+    }
+
+    /// Return conflation key of message sent to `channel`.
+    /// Return `None` if message shouldn't be conflated.
+    #[inline]
+    pub(crate) fn key(&self, channel: &str, msg: &RawValue) -> Option<Arc<str>> {
+        if let Some(ref key) = self.0 {
+            let data = serde_json::from_str(msg.get()).ok()?;
+            key(&data).map(|key| Arc::from(format!("{channel}\0{key}")))
+        } else {
+            Some(Arc::from(channel))
+        }
+    }
+}
+
+impl Debug for Conflation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Conflation")
+    }
+}
@@ -0,0 +1,76 @@
+use crate::types::{CookieId, BAYEUX_BROWSER};
+use axum_extra::extract::cookie::{Cookie, SameSite};
+use core::time::Duration;
+use std::borrow::Cow;
+
+/// Attributes of `BAYEUX_BROWSER` cookie.
+#[derive(Debug, Clone)]
+pub(crate) struct CookieConfig {
+    pub(crate) name: Cow<'static, str>,
+    pub(crate) path: Option<Cow<'static, str>>,
+    pub(crate) domain: Option<Cow<'static, str>>,
+    pub(crate) secure: bool,
+    pub(crate) http_only: bool,
+    pub(crate) same_site: Option<SameSite>,
+    pub(crate) max_age: Option<Duration>,
+}
+
+impl Default for CookieConfig {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            name: Cow::Borrowed(BAYEUX_BROWSER),
+            path: None,
+            domain: None,
+            secure: false,
+            http_only: false,
+            same_site: None,
+            max_age: None,
+        }
+    }
+}
+
+impl CookieConfig {
+    /// Warn about attributes, which make browsers drop the cookie.
+    #[inline]
+    pub(crate) fn check(&self) {
+        if self.same_site == Some(SameSite::None) && !self.secure {
+            tracing::warn!(
+                cookie = &*self.name,
+                "Cookie with `SameSite=None` but without `Secure` attribute is rejected by browsers, \
+                 enable `cookie_secure`."
+            );
+        }
+    }
+
+    #[inline]
+    pub(crate) fn build_cookie(&self, cookie_id: CookieId) -> Cookie<'static> {
+        let Self {
+            ref name,
+            ref path,
+            ref domain,
+            secure,
+            http_only,
+            same_site,
+            max_age,
+        } = *self;
+
+        let mut cookie = Cookie::new(name.clone(), cookie_id.to_string());
+        if let Some(path) = path.clone() {
+            cookie.set_path(path);
+        }
+        if let Some(domain) = domain.clone() {
+            cookie.set_domain(domain);
+        }
+        if secure {
+            cookie.set_secure(true);
+        }
+        if http_only {
+            cookie.set_http_only(true);
+        }
+        cookie.set_same_site(same_site);
+        cookie.set_max_age(max_age.and_then(|max_age| time::Duration::try_from(max_age).ok()));
+
+        cookie
+    }
+}
@@ -1,15 +1,27 @@
-use crate::{error::ParseError, types::Id};
+use crate::{error::ParseError, types::Id, IdGenerator};
 use core::fmt::{Debug, Display, Formatter};
+use sha2::{Digest, Sha256};
 
 pub(crate) const BAYEUX_BROWSER: &str = "BAYEUX_BROWSER";
 
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Clone, Copy, Hash, Eq, PartialEq)]
 pub(crate) struct CookieId(Id);
 
 impl CookieId {
     #[inline(always)]
-    pub(crate) fn gen() -> Self {
-        Self(Id::gen())
+    pub(crate) fn gen(generator: &dyn IdGenerator) -> Self {
+        Self(Id::gen(generator))
+    }
+
+    /// Derive id from arbitrary value (e.g. custom session header).
+    #[inline]
+    pub(crate) fn from_value(value: &[u8]) -> Self {
+        let mut id = [0u8; 20];
+        id.iter_mut()
+            .zip(Sha256::digest(value))
+            .for_each(|(dst, src)| *dst = src);
+
+        Self(Id::from_bytes(&id))
     }
 
     #[inline(always)]
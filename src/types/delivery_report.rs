@@ -0,0 +1,23 @@
+/// Result of publishing message through `LongPollingServiceContext::send`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DeliveryReport {
+    /// Channels with subscribers, which matched the published channel (including wildcards).
+    pub channels: Vec<String>,
+    /// Number of sessions, which got the message.
+    pub recipients: usize,
+    /// Number of sessions, which didn't get the message: removed before message was delivered
+    /// or [`blocked`](Self::blocked).
+    pub dropped: usize,
+    /// Number of sessions with full message queue. Message was dropped for them.
+    pub blocked: usize,
+    /// Message was suppressed by channel [`Throttle`](crate::Throttle).
+    pub throttled: bool,
+}
+
+impl DeliveryReport {
+    /// Return `true` if message wasn't delivered to any session.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.recipients == 0
+    }
+}
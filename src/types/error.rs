@@ -1,6 +1,12 @@
 mod http_handler_error;
+mod local_subscribe_error;
 mod parse_error;
+#[cfg(feature = "json-schema")]
+mod schema_error;
 mod send_error;
+mod subscribe_error;
 
-pub use send_error::*;
+#[cfg(feature = "json-schema")]
+pub use schema_error::*;
 pub(crate) use {http_handler_error::*, parse_error::*};
+pub use {local_subscribe_error::*, send_error::*, subscribe_error::*};
@@ -10,7 +10,7 @@ pub(crate) type HandlerResult<T> = Result<T, HandlerError>;
 #[derive(Debug)]
 pub(crate) enum HandlerError {
     StatusCode(StatusCode),
-    Message(Message),
+    Message(Box<Message>),
 }
 
 impl IntoResponse for HandlerError {
@@ -18,7 +18,7 @@ impl IntoResponse for HandlerError {
     fn into_response(self) -> Response {
         match self {
             HandlerError::StatusCode(code) => code.into_response(),
-            HandlerError::Message(message) => Json([message]).into_response(),
+            HandlerError::Message(message) => Json([*message]).into_response(),
         }
     }
 }
@@ -33,6 +33,6 @@ impl From<StatusCode> for HandlerError {
 impl From<Message> for HandlerError {
     #[inline(always)]
     fn from(message: Message) -> Self {
-        Self::Message(message)
+        Self::Message(Box::new(message))
     }
 }
@@ -0,0 +1,14 @@
+// https://github.com/rust-lang/rust-clippy/issues/10198
+#![allow(clippy::std_instead_of_core)]
+
+use crate::SubscribeError;
+
+/// Error returned by the `TypedChannel::subscribe`.
+#[allow(missing_docs)]
+#[derive(Debug, Copy, Clone, thiserror::Error)]
+pub enum LocalSubscribeError {
+    #[error("local session wasn't created")]
+    SessionWasntCreated,
+    #[error(transparent)]
+    Subscribe(#[from] SubscribeError),
+}
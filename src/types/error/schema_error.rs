@@ -0,0 +1,12 @@
+// https://github.com/rust-lang/rust-clippy/issues/10198
+#![allow(clippy::std_instead_of_core)]
+
+/// Error returned by the `LongPollingServiceContextBuilder::json_schema`.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("invalid JSON Schema of `{channel}` channel: {message}")]
+pub struct SchemaError {
+    /// Channel pattern, which schema was registered for.
+    pub channel: String,
+    /// Description of schema error.
+    pub message: String,
+}
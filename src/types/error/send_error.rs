@@ -2,7 +2,7 @@
 #![allow(clippy::std_instead_of_core)]
 
 use crate::ClientId;
-use tokio::sync::mpsc::error::SendError as TokioSendError;
+use tokio::sync::mpsc::error::{SendError as TokioSendError, TrySendError};
 
 /// Error returned by the `LongPoolingServiceContext::send`.
 #[allow(missing_docs)]
@@ -14,6 +14,10 @@ pub enum SendError {
     ClientWasntFound(ClientId),
     #[error("invalid channel name")]
     InvalidChannel,
+    #[error("message can't be serialized")]
+    InvalidMessage,
+    #[error("queue is full")]
+    Full,
 }
 
 impl<Msg> From<TokioSendError<Msg>> for SendError {
@@ -21,3 +25,12 @@ impl<Msg> From<TokioSendError<Msg>> for SendError {
         Self::Closed
     }
 }
+
+impl<Msg> From<TrySendError<Msg>> for SendError {
+    fn from(error: TrySendError<Msg>) -> Self {
+        match error {
+            TrySendError::Full(_) => Self::Full,
+            TrySendError::Closed(_) => Self::Closed,
+        }
+    }
+}
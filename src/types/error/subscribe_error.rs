@@ -0,0 +1,19 @@
+// https://github.com/rust-lang/rust-clippy/issues/10198
+#![allow(clippy::std_instead_of_core)]
+
+use crate::ClientId;
+
+/// Error returned by the `LongPoolingServiceContext::subscribe_client` and
+/// `LongPoolingServiceContext::unsubscribe_client`.
+#[allow(missing_docs)]
+#[derive(Debug, Copy, Clone, thiserror::Error)]
+pub enum SubscribeError {
+    #[error("client ({0}) wasn't found")]
+    ClientWasntFound(ClientId),
+    #[error("invalid channel name")]
+    InvalidChannel,
+    #[error("too many subscriptions")]
+    TooManySubscriptions,
+    #[error("too many channels")]
+    TooManyChannels,
+}
@@ -1,5 +1,6 @@
-use crate::ClientId;
+use crate::{ClientId, ReconnectAdvice, SessionAttributes};
 use axum::http::HeaderMap;
+use std::sync::Arc;
 
 #[allow(missing_docs)]
 #[derive(Debug)]
@@ -17,8 +18,28 @@ pub enum Event<AdditionalData, CustomData> {
         channels: Vec<String>,
         data: AdditionalData,
     },
+    /// Client was subscribed by server through `LongPollingServiceContext::subscribe_client`.
+    ServerSubscribe {
+        client_id: ClientId,
+        channels: Vec<String>,
+    },
+    /// Client was unsubscribed by server through `LongPollingServiceContext::unsubscribe_client`.
+    ServerUnsubscribe {
+        client_id: ClientId,
+        channels: Vec<String>,
+    },
+    /// Session was evicted by server through `LongPollingServiceContext::disconnect_client`.
+    SessionEvicted {
+        client_id: ClientId,
+        advice: ReconnectAdvice,
+    },
+    /// Messages of channel were suppressed by [`Throttle`](crate::Throttle) during interval.
+    MessagesSuppressed { channel: String, count: usize },
     /// Struct used in sessionRemoved callbacks.
-    SessionRemoved { client_id: ClientId },
+    SessionRemoved {
+        client_id: ClientId,
+        attributes: Arc<SessionAttributes>,
+    },
     /// Some custom data to send.
     CustomData(CustomData),
 }
@@ -1,7 +1,6 @@
-use crate::error::ParseError;
+use crate::{error::ParseError, IdGenerator};
 use core::fmt::{Debug, Display, Formatter};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::time::{SystemTime, UNIX_EPOCH};
 
 pub(crate) const ZERO_ID: Id = Id([0u32; 5]);
 
@@ -9,20 +8,17 @@ pub(crate) const ZERO_ID: Id = Id([0u32; 5]);
 pub(crate) struct Id([u32; 5]);
 
 impl Id {
-    #[inline]
-    pub(crate) fn gen() -> Self {
-        use rand::Rng;
-
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_nanos();
-
-        let lo = (timestamp & u128::from(u32::MAX)) as u32;
-        let mid = ((timestamp >> 32) & u128::from(u32::MAX)) as u32;
+    #[inline(always)]
+    pub(crate) fn gen(generator: &dyn IdGenerator) -> Self {
+        Self::from_bytes(&generator.generate())
+    }
 
-        let mut id = [mid, lo, 0, 0, 0];
-        rand::thread_rng().fill(&mut id[2..]);
+    #[inline]
+    pub(crate) fn from_bytes(bytes: &[u8; 20]) -> Self {
+        let mut id = [0u32; 5];
+        for (chunk, part) in bytes.chunks_exact(4).zip(&mut id) {
+            *part = u32::from_be_bytes(chunk.try_into().unwrap_or_default());
+        }
 
         Self(id)
     }
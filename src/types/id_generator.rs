@@ -0,0 +1,85 @@
+use core::fmt::Debug;
+use rand::RngCore as _;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Generator of `clientId` and `BAYEUX_BROWSER` cookie values.
+///
+/// Generated 20 bytes are sent as 40 hex characters.
+///
+/// Set with [`LongPollingServiceContextBuilder::id_generator`].
+///
+/// [`LongPollingServiceContextBuilder::id_generator`]: crate::LongPollingServiceContextBuilder::id_generator
+pub trait IdGenerator: Debug + Send + Sync {
+    /// Generate new id.
+    fn generate(&self) -> [u8; 20];
+}
+
+/// Nanosecond timestamp followed by 12 random bytes. Used by default.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TimestampIdGenerator;
+
+impl IdGenerator for TimestampIdGenerator {
+    #[inline]
+    fn generate(&self) -> [u8; 20] {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+
+        let mut id = [0u8; 20];
+        let (timestamp_part, random_part) = id.split_at_mut(8);
+        timestamp_part.copy_from_slice(&timestamp.to_be_bytes());
+        rand::thread_rng().fill_bytes(random_part);
+
+        id
+    }
+}
+
+/// 20 bytes from cryptographically secure random number generator.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RandomIdGenerator;
+
+impl IdGenerator for RandomIdGenerator {
+    #[inline]
+    fn generate(&self) -> [u8; 20] {
+        let mut id = [0u8; 20];
+        rand::thread_rng().fill_bytes(&mut id);
+
+        id
+    }
+}
+
+/// Node id followed by 16 random bytes. Ids from different nodes of cluster never collide.
+///
+/// # Example
+/// ```rust,no_run
+/// use axum_cometd::{LongPollingServiceContextBuilder, NodeIdGenerator};
+///
+/// let context = LongPollingServiceContextBuilder::new()
+///     .id_generator(NodeIdGenerator::new(3))
+///     .build::<(), ()>();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NodeIdGenerator {
+    node: u32,
+}
+
+impl NodeIdGenerator {
+    /// Construct a new `NodeIdGenerator` for `node`.
+    #[inline(always)]
+    pub const fn new(node: u32) -> Self {
+        Self { node }
+    }
+}
+
+impl IdGenerator for NodeIdGenerator {
+    #[inline]
+    fn generate(&self) -> [u8; 20] {
+        let mut id = [0u8; 20];
+        let (node_part, random_part) = id.split_at_mut(4);
+        node_part.copy_from_slice(&self.node.to_be_bytes());
+        rand::thread_rng().fill_bytes(random_part);
+
+        id
+    }
+}
@@ -1,12 +1,14 @@
 mod de;
 
-use crate::types::{ChannelId, ClientId};
+use crate::types::{ClientId, PayloadError, Priority, ReconnectAdvice};
 use axum::Json;
 use core::fmt::Debug;
 use core::time::Duration;
-use serde::{Deserialize, Serialize};
-use serde_json::Value as JsonValue;
+use serde::{de::DeserializeSeed as _, Deserialize, Serialize};
+use serde_json::{value::RawValue, Value as JsonValue};
 use serde_with::skip_serializing_none;
+use std::sync::Arc;
+use tokio::time::Instant;
 
 #[skip_serializing_none]
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -14,8 +16,8 @@ pub(crate) struct Advice {
     pub(crate) interval: Option<u64>,
     #[serde(rename = "maxInterval")]
     pub(crate) max_interval: Option<u64>,
-    //#[serde(rename = "multiple-clients")]
-    //pub(crate) multiple_clients: Option<bool>,
+    #[serde(rename = "multiple-clients")]
+    pub(crate) multiple_clients: Option<bool>,
     pub(crate) reconnect: Option<Reconnect>,
     pub(crate) timeout: Option<u64>,
     pub(crate) hosts: Option<Vec<String>>,
@@ -32,6 +34,14 @@ impl Advice {
         }
     }
 
+    #[inline(always)]
+    pub(crate) fn multiple_clients(timeout: Duration, interval: Duration) -> Self {
+        Self {
+            multiple_clients: Some(true),
+            ..Self::retry(timeout, interval)
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn handshake() -> Self {
         Self {
@@ -42,6 +52,24 @@ impl Advice {
     }
 }
 
+impl From<ReconnectAdvice> for Advice {
+    #[inline]
+    fn from(advice: ReconnectAdvice) -> Self {
+        match advice {
+            ReconnectAdvice::None => Self {
+                reconnect: Some(Reconnect::None),
+                ..Default::default()
+            },
+            ReconnectAdvice::Handshake => Self::handshake(),
+            ReconnectAdvice::Retry(interval) => Self {
+                reconnect: Some(Reconnect::Retry),
+                interval: Some(interval.as_millis().try_into().unwrap_or(u64::MAX)),
+                ..Default::default()
+            },
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum Reconnect {
@@ -59,9 +87,10 @@ pub(crate) struct Message {
     pub(crate) client_id: Option<ClientId>,
     //#[serde(rename = "connectionType")]
     //pub(crate) connection_type: Option<String>,
-    pub(crate) data: Option<JsonValue>,
+    /// Kept raw, so published data is forwarded to subscribers without re-serialization.
+    pub(crate) data: Option<Arc<RawValue>>,
     pub(crate) error: Option<String>,
-    //pub(crate) ext: Option<JsonValue>,
+    pub(crate) ext: Option<JsonValue>,
     pub(crate) id: Option<String>,
     #[serde(rename = "minimumVersion")]
     pub(crate) minimum_version: Option<String>,
@@ -75,13 +104,25 @@ pub(crate) struct Message {
     pub(crate) version: Option<String>,
 }
 
+/// Message is serialized once and shared between all subscribers.
 #[derive(Debug, Clone)]
 pub(crate) struct SubscriptionMessage {
-    pub(crate) channel: ChannelId,
-    pub(crate) msg: JsonValue,
+    pub(crate) channel: Arc<str>,
+    pub(crate) msg: Arc<RawValue>,
+    /// Older pending message with the same key is dropped from client queue.
+    pub(crate) conflation_key: Option<Arc<str>>,
+    /// Message is dropped from client queue after this moment.
+    pub(crate) expires_at: Option<Instant>,
+    /// Urgent messages overtake normal ones in client queue.
+    pub(crate) priority: Priority,
 }
 
 impl Message {
+    #[inline(always)]
+    pub(crate) fn ext_token(ext: Option<&JsonValue>) -> Option<&str> {
+        ext?.get("token")?.as_str()
+    }
+
     #[inline(always)]
     pub(crate) fn ok(id: Option<String>, channel: Option<String>) -> Self {
         Self {
@@ -133,6 +174,59 @@ impl Message {
         }
     }
 
+    #[inline(always)]
+    fn error(id: Option<String>, channel: Option<String>, error: &str) -> Self {
+        Self {
+            id,
+            successful: Some(false),
+            channel,
+            error: Some(error.into()),
+            ..Default::default()
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn rate_limit_exceeded(id: Option<String>, channel: Option<String>) -> Self {
+        Self::error(id, channel, "429::rate_limit_exceeded")
+    }
+
+    #[inline(always)]
+    pub(crate) fn message_too_large(id: Option<String>, channel: Option<String>) -> Self {
+        Self::error(id, channel, "413::message_too_large")
+    }
+
+    #[inline(always)]
+    pub(crate) fn invalid_payload(
+        id: Option<String>,
+        channel: Option<String>,
+        error: &PayloadError,
+    ) -> Self {
+        Self::error(
+            id,
+            channel,
+            &format!("400:{}:{}", error.path, error.message),
+        )
+    }
+
+    #[inline(always)]
+    pub(crate) fn batch_too_large() -> Self {
+        Self::error(None, None, "413::batch_too_large")
+    }
+
+    #[inline(always)]
+    pub(crate) fn too_many_subscriptions(id: Option<String>) -> Self {
+        Self::error(
+            id,
+            Some("/meta/subscribe".into()),
+            "403::too_many_subscriptions",
+        )
+    }
+
+    #[inline(always)]
+    pub(crate) fn too_many_channels(id: Option<String>) -> Self {
+        Self::error(id, Some("/meta/subscribe".into()), "403::too_many_channels")
+    }
+
     #[inline(always)]
     pub(crate) fn channel_missing(id: Option<String>) -> Self {
         Self {
@@ -144,6 +238,35 @@ impl Message {
     }
 }
 
+/// Messages of `/connect` request.
+#[derive(Debug)]
+pub(crate) enum Batch {
+    Messages(Vec<Message>),
+    /// Batch is longer than limit, contains only first messages.
+    TooLarge(Vec<Message>),
+}
+
+impl Batch {
+    /// Deserialize batch from `body`. Parsing is stopped as soon as batch exceed `max_length`.
+    /// Trailing data after complete batch is an error.
+    #[inline]
+    pub(crate) fn parse(body: &[u8], max_length: Option<usize>) -> serde_json::Result<Self> {
+        let mut messages = Vec::new();
+        let mut deserializer = serde_json::Deserializer::from_slice(body);
+        let result = de::BatchSeed {
+            max_length: max_length.unwrap_or(usize::MAX),
+            messages: &mut messages,
+        }
+        .deserialize(&mut deserializer);
+
+        match result {
+            _ if max_length.is_some_and(|max| messages.len() > max) => Ok(Self::TooLarge(messages)),
+            Ok(()) => deserializer.end().map(|()| Self::Messages(messages)),
+            Err(error) => Err(error),
+        }
+    }
+}
+
 impl From<Message> for Json<[Message; 1]> {
     #[inline(always)]
     fn from(message: Message) -> Self {
@@ -1,4 +1,52 @@
-use serde::{Deserialize, Deserializer};
+use super::Message;
+use core::fmt::Formatter;
+use serde::{
+    de::{DeserializeSeed, Error as _, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+/// Deserialize messages of batch into `messages`, but stop after `max_length + 1` of them,
+/// so too big batch doesn't allocate more than `max_length + 1` messages.
+pub(super) struct BatchSeed<'a> {
+    pub(super) max_length: usize,
+    pub(super) messages: &'a mut Vec<Message>,
+}
+
+impl<'de> DeserializeSeed<'de> for BatchSeed<'_> {
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for BatchSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("a sequence of messages")
+    }
+
+    #[inline]
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        while let Some(message) = seq.next_element()? {
+            self.messages.push(message);
+
+            if self.messages.len() > self.max_length {
+                return Err(A::Error::custom("batch is too large"));
+            }
+        }
+
+        Ok(())
+    }
+}
 
 #[inline(always)]
 pub(super) fn deserialize_subscription<'de, D>(
@@ -64,4 +112,32 @@ mod tests {
             None
         );
     }
+
+    #[test]
+    fn test_batch_parsing_stops_at_limit() {
+        use crate::messages::Batch;
+
+        // Garbage after third message isn't parsed.
+        let body = br#"[{"id":"0"},{"id":"1"},{"id":"2"},garbage"#;
+        assert!(matches!(
+            Batch::parse(body, Some(2)),
+            Ok(Batch::TooLarge(messages)) if messages.len() == 3
+        ));
+        assert!(Batch::parse(body, None).is_err());
+
+        let body = br#"[{"id":"0"},{"id":"1"}]"#;
+        assert!(matches!(
+            Batch::parse(body, Some(2)),
+            Ok(Batch::Messages(messages)) if messages.len() == 2
+        ));
+    }
+
+    #[test]
+    fn test_batch_parsing_rejects_trailing_data() {
+        use crate::messages::Batch;
+
+        assert!(Batch::parse(br#"[{"id":"0"}] "#, None).is_ok());
+        assert!(Batch::parse(br#"[{"id":"0"}]garbage"#, None).is_err());
+        assert!(Batch::parse(br#"[{"id":"0"}][{"id":"1"}]"#, Some(2)).is_err());
+    }
 }
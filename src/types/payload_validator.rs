@@ -0,0 +1,60 @@
+use core::fmt::{Debug, Formatter};
+use serde_json::value::RawValue;
+
+/// Validator of published message `data`.
+pub(crate) enum PayloadValidator {
+    /// Data must be deserializable into type of `TypedChannel`.
+    Typed(fn(&str) -> bool),
+    /// Data must match JSON Schema.
+    #[cfg(feature = "json-schema")]
+    JsonSchema(Box<jsonschema::Validator>),
+}
+
+/// Reason why published message `data` was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PayloadError {
+    /// JSON Pointer to invalid part of data. Empty for whole data.
+    pub(crate) path: String,
+    pub(crate) message: String,
+}
+
+impl PayloadError {
+    #[inline(always)]
+    pub(crate) fn invalid_payload() -> Self {
+        Self {
+            path: String::new(),
+            message: "invalid_payload".to_owned(),
+        }
+    }
+}
+
+impl Debug for PayloadValidator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            Self::Typed(_) => f.write_str("Typed"),
+            #[cfg(feature = "json-schema")]
+            Self::JsonSchema(_) => f.write_str("JsonSchema"),
+        }
+    }
+}
+
+impl PayloadValidator {
+    #[inline]
+    pub(crate) fn validate(&self, data: &RawValue) -> Result<(), PayloadError> {
+        match *self {
+            Self::Typed(validate) => validate(data.get())
+                .then_some(())
+                .ok_or_else(PayloadError::invalid_payload),
+            #[cfg(feature = "json-schema")]
+            Self::JsonSchema(ref validator) => {
+                let data = serde_json::from_str(data.get())
+                    .map_err(|_| PayloadError::invalid_payload())?;
+
+                validator.validate(&data).map_err(|error| PayloadError {
+                    path: error.instance_path.to_string(),
+                    message: error.to_string(),
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+/// Delivery priority of message within client queue.
+///
+/// Urgent messages are delivered on the next `/meta/connect` before all pending normal ones.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Priority {
+    /// Message is delivered in publication order.
+    #[default]
+    Normal,
+    /// Message overtakes pending normal messages.
+    Urgent,
+}
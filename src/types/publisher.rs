@@ -0,0 +1,173 @@
+use crate::{
+    context::serialize, messages::SubscriptionMessage, ChannelNameValidator,
+    LongPollingServiceContext, Priority, SendError,
+};
+use core::{fmt::Debug, time::Duration};
+use serde::Serialize;
+use std::sync::{Arc, Weak};
+use tokio::{
+    sync::mpsc::{self, error::SendTimeoutError},
+    task::AbortHandle,
+};
+
+/// Cloneable handle to publish messages through bounded queue of `LongPollingServiceContext`.
+///
+/// Handle doesn't keep context alive and can be used from synchronous code and other runtimes.
+/// Delivery task is aborted when context is dropped, after that [`SendError::Closed`] is returned.
+/// Messages, which are still in queue at that moment, are discarded.
+/// Messages are delivered in background, see [`LongPollingServiceContext::publisher`].
+///
+/// # Example
+/// ```rust,no_run
+/// # async {
+/// let context = axum_cometd::LongPollingServiceContextBuilder::new().build::<(), ()>();
+/// let publisher = context.publisher();
+///
+/// std::thread::spawn(move || {
+///     publisher.blocking_send("/topic", "Hello from thread").unwrap();
+/// });
+/// # };
+/// ```
+#[derive(Debug, Clone)]
+pub struct Publisher {
+    tx: mpsc::Sender<SubscriptionMessage>,
+    task: Weak<PublisherTask>,
+    channel_name_validator: Arc<dyn ChannelNameValidator>,
+}
+
+/// Delivery task of [`Publisher`], owned by context. Task is aborted on drop.
+#[derive(Debug)]
+pub(crate) struct PublisherTask(AbortHandle);
+
+impl Drop for PublisherTask {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+impl Publisher {
+    #[inline]
+    pub(crate) fn spawn<AdditionalData, CustomData>(
+        context: &Arc<LongPollingServiceContext<AdditionalData, CustomData>>,
+        capacity: usize,
+    ) -> (Self, Arc<PublisherTask>)
+    where
+        AdditionalData: Send + Sync + 'static,
+        CustomData: Send + Sync + 'static,
+    {
+        let (tx, mut rx) = mpsc::channel::<SubscriptionMessage>(capacity);
+        let channel_name_validator = Arc::clone(&context.channel_name_validator);
+        let context = Arc::downgrade(context);
+
+        let task = tokio::task::spawn(async move {
+            while let Some(SubscriptionMessage { channel, msg, .. }) = rx.recv().await {
+                let Some(context) = Weak::upgrade(&context) else {
+                    break;
+                };
+
+                if let Err(error) = context.send_raw(&channel, msg).await {
+                    tracing::error!(
+                        channel = %channel,
+                        "Can't publish message to `{channel}` channel: {error}."
+                    );
+                }
+                context.arm_throttle_timer();
+            }
+        });
+        let task = Arc::new(PublisherTask(task.abort_handle()));
+
+        (
+            Self {
+                tx,
+                task: Arc::downgrade(&task),
+                channel_name_validator,
+            },
+            task,
+        )
+    }
+
+    /// Enqueue message without waiting.
+    /// Return [`SendError::Full`] if queue is full.
+    #[inline]
+    pub fn try_send(
+        &self,
+        channel: &str,
+        message: impl Debug + Serialize,
+    ) -> Result<(), SendError> {
+        let message = self.message(channel, &message)?;
+
+        Ok(self.tx.try_send(message)?)
+    }
+
+    /// Enqueue message, waiting for free space in queue.
+    #[inline]
+    pub async fn send(
+        &self,
+        channel: &str,
+        message: impl Debug + Serialize,
+    ) -> Result<(), SendError> {
+        let message = self.message(channel, &message)?;
+
+        Ok(self.tx.send(message).await?)
+    }
+
+    /// Enqueue message, waiting for free space in queue at most `timeout`.
+    /// Return [`SendError::Full`] if timeout elapsed.
+    #[inline]
+    pub async fn send_timeout(
+        &self,
+        channel: &str,
+        message: impl Debug + Serialize,
+        timeout: Duration,
+    ) -> Result<(), SendError> {
+        let message = self.message(channel, &message)?;
+
+        self.tx
+            .send_timeout(message, timeout)
+            .await
+            .map_err(|error| match error {
+                SendTimeoutError::Timeout(_) => SendError::Full,
+                SendTimeoutError::Closed(_) => SendError::Closed,
+            })
+    }
+
+    /// Enqueue message from synchronous code, blocking current thread while queue is full.
+    ///
+    /// # Panics
+    /// This function panics if called within an asynchronous execution context.
+    #[inline]
+    pub fn blocking_send(
+        &self,
+        channel: &str,
+        message: impl Debug + Serialize,
+    ) -> Result<(), SendError> {
+        let message = self.message(channel, &message)?;
+
+        Ok(self.tx.blocking_send(message)?)
+    }
+
+    #[inline]
+    fn message(
+        &self,
+        channel: &str,
+        message: &(impl Debug + Serialize),
+    ) -> Result<SubscriptionMessage, SendError> {
+        self.channel_name_validator
+            .validate_send_channel_name(channel)
+            .then_some(())
+            .ok_or(SendError::InvalidChannel)?;
+        // Queue is closed only after aborted task is dropped by runtime.
+        if self.task.strong_count() == 0 {
+            return Err(SendError::Closed);
+        }
+
+        Ok(SubscriptionMessage {
+            channel: Arc::from(channel),
+            msg: serialize(message)?,
+            conflation_key: None,
+            expires_at: None,
+            priority: Priority::Normal,
+        })
+    }
+}
@@ -0,0 +1,48 @@
+/// Token-bucket rate limit for client publishes and subscribes.
+///
+/// # Example
+/// ```rust,no_run
+/// use axum_cometd::{LongPollingServiceContextBuilder, RateLimit};
+///
+/// let context = LongPollingServiceContextBuilder::new()
+///     .client_rate_limit(
+///         RateLimit::new()
+///             .messages_per_second(10)
+///             .bytes_per_second(64 * 1024),
+///     )
+///     .build::<(), ()>();
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RateLimit {
+    pub(crate) messages_per_second: Option<u32>,
+    pub(crate) bytes_per_second: Option<u32>,
+}
+
+impl RateLimit {
+    /// Construct a new `RateLimit` without any limits.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            messages_per_second: None,
+            bytes_per_second: None,
+        }
+    }
+
+    /// Set maximum number of messages per second.
+    #[inline(always)]
+    #[must_use]
+    pub const fn messages_per_second(mut self, messages: u32) -> Self {
+        self.messages_per_second = Some(messages);
+        self
+    }
+
+    /// Set maximum number of `data` bytes per second.
+    /// Message with bigger `data` can never pass, so it is rejected with `413::message_too_large`
+    /// error and isn't counted as rate limit violation.
+    #[inline(always)]
+    #[must_use]
+    pub const fn bytes_per_second(mut self, bytes: u32) -> Self {
+        self.bytes_per_second = Some(bytes);
+        self
+    }
+}
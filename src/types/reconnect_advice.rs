@@ -0,0 +1,12 @@
+use core::time::Duration;
+
+/// Reconnect advice which will be sent to client on server-side disconnect.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ReconnectAdvice {
+    /// Client must not reconnect.
+    None,
+    /// Client must do new handshake.
+    Handshake,
+    /// Client must retry connect after given interval.
+    Retry(Duration),
+}
@@ -0,0 +1,54 @@
+use crate::utils::ScheduledEntries;
+use std::sync::Weak;
+use tokio::time::Instant;
+
+/// Message scheduled with [`LongPollingServiceContext::send_at`].
+///
+/// [`LongPollingServiceContext::send_at`]: crate::LongPollingServiceContext::send_at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledMessage {
+    /// Id of scheduled message, see [`ScheduleHandle::id`].
+    pub id: u64,
+    /// Channel, which message will be sent to.
+    pub channel: String,
+    /// When message will be sent.
+    pub send_at: Instant,
+}
+
+/// Handle to cancel scheduled message.
+/// Dropping handle doesn't cancel message.
+#[derive(Debug, Clone)]
+pub struct ScheduleHandle {
+    id: u64,
+    entries: Weak<ScheduledEntries>,
+}
+
+impl ScheduleHandle {
+    #[inline(always)]
+    pub(crate) const fn new(id: u64, entries: Weak<ScheduledEntries>) -> Self {
+        Self { id, entries }
+    }
+
+    /// Return id of scheduled message.
+    #[inline(always)]
+    pub const fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Cancel scheduled message.
+    /// Return `false` if message was already sent or cancelled.
+    #[inline]
+    pub fn cancel(&self) -> bool {
+        self.entries
+            .upgrade()
+            .is_some_and(|entries| entries.remove(self.id))
+    }
+
+    /// Return `true` if message wasn't sent or cancelled yet.
+    #[inline]
+    pub fn is_pending(&self) -> bool {
+        self.entries
+            .upgrade()
+            .is_some_and(|entries| entries.contains(self.id))
+    }
+}
@@ -0,0 +1,86 @@
+use ahash::HashMap;
+use core::any::{Any, TypeId};
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// Typed attributes of client session. One value per type.
+///
+/// Handshake `AdditionalData` is stored here automatically.
+///
+/// # Example
+/// ```rust,no_run
+/// # async fn f(context: std::sync::Arc<axum_cometd::LongPollingServiceContext<(), ()>>, client_id: axum_cometd::ClientId) {
+/// #[derive(Debug)]
+/// struct UserId(u64);
+///
+/// if let Some(session) = context.session(&client_id).await {
+///     session.insert(UserId(42));
+///     assert_eq!(session.get::<UserId>().map(|user_id| user_id.0), Some(42));
+/// }
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct SessionAttributes {
+    attributes: RwLock<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
+}
+
+impl SessionAttributes {
+    /// Insert attribute. Return previous attribute of the same type.
+    #[inline]
+    pub fn insert<T>(&self, value: T) -> Option<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.attributes
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(TypeId::of::<T>(), Arc::new(value))
+            .and_then(downcast)
+    }
+
+    /// Get attribute.
+    #[inline]
+    pub fn get<T>(&self) -> Option<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.attributes
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&TypeId::of::<T>())
+            .cloned()
+            .and_then(downcast)
+    }
+
+    /// Remove attribute.
+    #[inline]
+    pub fn remove<T>(&self) -> Option<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.attributes
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&TypeId::of::<T>())
+            .and_then(downcast)
+    }
+
+    /// Return `true` if attribute of type `T` is set.
+    #[inline]
+    pub fn contains<T>(&self) -> bool
+    where
+        T: Send + Sync + 'static,
+    {
+        self.attributes
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(&TypeId::of::<T>())
+    }
+}
+
+#[inline(always)]
+fn downcast<T>(value: Arc<dyn Any + Send + Sync>) -> Option<Arc<T>>
+where
+    T: Send + Sync + 'static,
+{
+    value.downcast().ok()
+}
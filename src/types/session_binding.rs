@@ -0,0 +1,119 @@
+use crate::{
+    types::{ClientId, CookieId},
+    CookieJarExt as _,
+};
+use axum::http::{HeaderMap, HeaderName};
+use axum_extra::extract::CookieJar;
+use core::fmt::{Debug, Formatter};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Arc;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// How server bind `clientId` to requests.
+#[derive(Clone, Default)]
+pub enum SessionBinding {
+    /// Bind `clientId` to `BAYEUX_BROWSER` cookie.
+    #[default]
+    Cookie,
+    /// Bind `clientId` to value of custom request header.
+    Header(HeaderName),
+    /// Check only `clientId`.
+    /// If secret is set, server return signed token in `ext.token` field of handshake response
+    /// and client must send it in `ext.token` field of every next message.
+    ClientId {
+        /// Secret to sign tokens.
+        secret: Option<Arc<[u8]>>,
+    },
+}
+
+impl Debug for SessionBinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            Self::Cookie => f.write_str("Cookie"),
+            Self::Header(ref name) => f.debug_tuple("Header").field(name).finish(),
+            Self::ClientId { ref secret } => f
+                .debug_struct("ClientId")
+                .field("secret", &secret.as_ref().map(|_| "***"))
+                .finish(),
+        }
+    }
+}
+
+/// Session binding extracted from request.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum RequestBinding {
+    Browser(CookieId),
+    ClientId,
+}
+
+impl SessionBinding {
+    /// Extract binding from request. Return `None` if required cookie or header is missing.
+    #[inline]
+    pub(crate) fn extract(
+        &self,
+        cookie_name: &str,
+        jar: &CookieJar,
+        headers: &HeaderMap,
+    ) -> Option<RequestBinding> {
+        match *self {
+            Self::Cookie => jar.get_cookie_id(cookie_name).map(RequestBinding::Browser),
+            Self::Header(ref name) => headers
+                .get(name)
+                .map(|value| RequestBinding::Browser(CookieId::from_value(value.as_bytes()))),
+            Self::ClientId { .. } => Some(RequestBinding::ClientId),
+        }
+    }
+
+    /// Sign `clientId`. Return `None` if there is no secret.
+    #[inline]
+    pub(crate) fn sign(&self, client_id: &ClientId) -> Option<String> {
+        let mac = self.mac(client_id)?;
+
+        Some(
+            mac.finalize()
+                .into_bytes()
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect(),
+        )
+    }
+
+    /// Verify token of `clientId`. Always `true` if there is no secret.
+    #[inline]
+    pub(crate) fn verify(&self, client_id: &ClientId, token: Option<&str>) -> bool {
+        match (self.mac(client_id), token.and_then(decode_hex)) {
+            (None, _) => true,
+            (Some(mac), Some(token)) => mac.verify_slice(&token).is_ok(),
+            (Some(_), None) => false,
+        }
+    }
+
+    #[inline]
+    fn mac(&self, client_id: &ClientId) -> Option<HmacSha256> {
+        match *self {
+            Self::ClientId {
+                secret: Some(ref secret),
+            } => {
+                let mut mac = HmacSha256::new_from_slice(secret).ok()?;
+                mac.update(client_id.to_string().as_bytes());
+                Some(mac)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[inline]
+fn decode_hex(str: &str) -> Option<Vec<u8>> {
+    str.len().is_multiple_of(2).then_some(())?;
+
+    (0..str.len())
+        .step_by(2)
+        .map(|i| {
+            str.get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+        })
+        .collect()
+}
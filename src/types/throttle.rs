@@ -0,0 +1,57 @@
+use core::time::Duration;
+
+/// Throughput limit of channel, applied before message is sent to subscribers.
+///
+/// # Example
+/// ```rust,no_run
+/// use axum_cometd::{LongPollingServiceContextBuilder, Throttle};
+/// use core::time::Duration;
+///
+/// let context = LongPollingServiceContextBuilder::new()
+///     .throttle("/prices/*", Throttle::new(10, Duration::from_secs(1)).merge_latest())
+///     .build::<(), ()>();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Throttle {
+    pub(crate) max_messages: usize,
+    pub(crate) interval: Duration,
+    pub(crate) mode: ThrottleMode,
+}
+
+/// What to do with messages over [`Throttle`] limit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleMode {
+    /// Drop messages over limit.
+    #[default]
+    DropExcess,
+    /// Keep the newest message over limit and send it at the end of interval.
+    MergeLatest,
+}
+
+impl Throttle {
+    /// Construct a new `Throttle`, which allows at most `max_messages` messages per `interval`
+    /// and drops the excess.
+    #[inline(always)]
+    pub const fn new(max_messages: usize, interval: Duration) -> Self {
+        Self {
+            max_messages,
+            interval,
+            mode: ThrottleMode::DropExcess,
+        }
+    }
+
+    /// Set what to do with messages over limit.
+    #[inline(always)]
+    #[must_use]
+    pub const fn mode(mut self, mode: ThrottleMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Keep the newest message over limit and send it at the end of interval.
+    #[inline(always)]
+    #[must_use]
+    pub const fn merge_latest(self) -> Self {
+        self.mode(ThrottleMode::MergeLatest)
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{
+    types::{ClientId, ClientReceiver, CookieId, PayloadValidator},
+    DeliveryReport, LocalSubscribeError, LongPollingServiceContext, SendError, SubscribeError,
+};
+use core::{
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::sync::Arc;
+use tokio::runtime::Handle;
+
+/// Channel with payload of type `T`.
+///
+/// Register channel with [`LongPollingServiceContextBuilder::typed_channel`]
+/// to reject client publishes, which can't be deserialized into `T`,
+/// with `400::invalid_payload` error.
+///
+/// # Example
+/// ```rust,no_run
+/// use axum_cometd::{LongPollingServiceContextBuilder, TypedChannel};
+///
+/// #[derive(Debug, serde::Serialize, serde::Deserialize)]
+/// struct Price {
+///     symbol: String,
+///     value: f64,
+/// }
+///
+/// const PRICES: TypedChannel<Price> = TypedChannel::new("/prices");
+///
+/// # async {
+/// let context = LongPollingServiceContextBuilder::new()
+///     .typed_channel(&PRICES)
+///     .build::<(), ()>();
+///
+/// let mut rx = PRICES.subscribe(&context).await?;
+/// PRICES
+///     .send(
+///         &context,
+///         &Price {
+///             symbol: "ABC".to_owned(),
+///             value: 1.0,
+///         },
+///     )
+///     .await?;
+///
+/// while let Some(price) = rx.recv().await {
+///     println!("Got price: `{price:?}`");
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # };
+/// ```
+///
+/// [`LongPollingServiceContextBuilder::typed_channel`]: crate::LongPollingServiceContextBuilder::typed_channel
+pub struct TypedChannel<T> {
+    name: &'static str,
+    _marker: PhantomData<fn(T) -> T>,
+}
+
+impl<T> Debug for TypedChannel<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("TypedChannel").field(&self.name).finish()
+    }
+}
+
+impl<T> Clone for TypedChannel<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TypedChannel<T> {}
+
+impl<T> TypedChannel<T> {
+    /// Construct a new `TypedChannel` with `name`.
+    #[inline(always)]
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Return channel name.
+    #[inline(always)]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T> TypedChannel<T>
+where
+    T: Debug + Serialize + DeserializeOwned,
+{
+    /// Send message to channel.
+    #[inline]
+    pub async fn send<AdditionalData, CustomData>(
+        &self,
+        context: &LongPollingServiceContext<AdditionalData, CustomData>,
+        message: &T,
+    ) -> Result<DeliveryReport, SendError> {
+        context.send(self.name, message).await
+    }
+
+    /// Subscribe server-side local session on channel.
+    ///
+    /// Local session doesn't time out: it lives until returned receiver is dropped.
+    #[inline]
+    pub async fn subscribe<AdditionalData, CustomData>(
+        &self,
+        context: &Arc<LongPollingServiceContext<AdditionalData, CustomData>>,
+    ) -> Result<TypedReceiver<T>, LocalSubscribeError>
+    where
+        AdditionalData: Send + Sync + 'static,
+        CustomData: Send + Sync + 'static,
+    {
+        let client_id = context
+            .register(
+                CookieId::gen(&*context.id_generator),
+                Default::default(),
+                None,
+                true,
+            )
+            .await
+            .ok_or(LocalSubscribeError::SessionWasntCreated)?;
+        // Created right away, so session is removed if subscription fails.
+        let session = LocalSession::new(context, client_id);
+        let rx = context
+            .get_client_receiver(&client_id)
+            .await
+            .ok_or(SubscribeError::ClientWasntFound(client_id))?;
+
+        context
+            .subscribe_client(client_id, &[self.name.to_owned()])
+            .await?;
+
+        Ok(TypedReceiver {
+            client_id,
+            rx,
+            _session: session,
+            _marker: PhantomData,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn validator(&self) -> (&'static str, PayloadValidator) {
+        (
+            self.name,
+            PayloadValidator::Typed(|data| serde_json::from_str::<T>(data).is_ok()),
+        )
+    }
+}
+
+/// Receiver of local session subscribed through [`TypedChannel::subscribe`].
+pub struct TypedReceiver<T> {
+    client_id: ClientId,
+    rx: ClientReceiver,
+    _session: LocalSession,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Removes local session on drop.
+struct LocalSession(Option<Box<dyn FnOnce() + Send + Sync>>);
+
+impl LocalSession {
+    #[inline]
+    fn new<AdditionalData, CustomData>(
+        context: &Arc<LongPollingServiceContext<AdditionalData, CustomData>>,
+        client_id: ClientId,
+    ) -> Self
+    where
+        AdditionalData: Send + Sync + 'static,
+        CustomData: Send + Sync + 'static,
+    {
+        let context = Arc::downgrade(context);
+
+        Self(Some(Box::new(move || {
+            let Ok(runtime) = Handle::try_current() else {
+                tracing::warn!(
+                    client_id = %client_id,
+                    "Local session `{client_id}` was dropped outside of tokio runtime and wasn't removed."
+                );
+                return;
+            };
+
+            runtime.spawn(async move {
+                if let Some(context) = context.upgrade() {
+                    context.unsubscribe(client_id).await;
+                }
+            });
+        })))
+    }
+}
+
+impl Drop for LocalSession {
+    fn drop(&mut self) {
+        if let Some(remove) = self.0.take() {
+            remove();
+        }
+    }
+}
+
+impl<T> Debug for TypedReceiver<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TypedReceiver")
+            .field("client_id", &self.client_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> TypedReceiver<T>
+where
+    T: DeserializeOwned,
+{
+    /// Return `clientId` of local session.
+    #[inline(always)]
+    pub const fn client_id(&self) -> ClientId {
+        self.client_id
+    }
+
+    /// Receive next message.
+    /// Messages, which can't be deserialized into `T`, are skipped.
+    /// Return `None` if local session was removed.
+    #[inline]
+    pub async fn recv(&mut self) -> Option<T> {
+        loop {
+            let message = self.rx.recv().await.ok()?;
+
+            match serde_json::from_str(message.msg.get()) {
+                Ok(message) => return Some(message),
+                Err(error) => {
+                    tracing::warn!(
+                        client_id = %self.client_id,
+                        channel = &*message.channel,
+                        "Message `{}` can't be deserialized: {error}.",
+                        message.msg
+                    );
+                }
+            }
+        }
+    }
+}
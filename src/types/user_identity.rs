@@ -0,0 +1,38 @@
+use crate::SessionAttributes;
+use axum::http::HeaderMap;
+use core::fmt::{Debug, Formatter};
+use serde_json::Value as JsonValue;
+
+type UserIdentityFn =
+    dyn Fn(&HeaderMap, Option<&JsonValue>, &SessionAttributes) -> Option<String> + Send + Sync;
+
+/// Extract application user identity from handshake headers, `ext` field and session attributes.
+pub(crate) struct UserIdentity(Box<UserIdentityFn>);
+
+impl UserIdentity {
+    #[inline(always)]
+    pub(crate) fn new(
+        extract: impl Fn(&HeaderMap, Option<&JsonValue>, &SessionAttributes) -> Option<String>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Self(Box::new(extract))
+    }
+
+    #[inline(always)]
+    pub(crate) fn extract(
+        &self,
+        headers: &HeaderMap,
+        ext: Option<&JsonValue>,
+        attributes: &SessionAttributes,
+    ) -> Option<String> {
+        (self.0)(headers, ext, attributes)
+    }
+}
+
+impl Debug for UserIdentity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("UserIdentity")
+    }
+}
@@ -1,7 +1,15 @@
-mod channel_name_validation;
+mod channel_throttler;
+mod evicted_clients;
+mod long_polls_counter;
+mod rate_limiter;
+mod scheduler;
 #[cfg(test)]
 mod test;
+mod users_index;
 mod wildnames;
 mod wildnames_cache;
 
-pub(crate) use {channel_name_validation::*, wildnames::*, wildnames_cache::*};
+pub(crate) use {
+    channel_throttler::*, evicted_clients::*, long_polls_counter::*, rate_limiter::*, scheduler::*,
+    users_index::*, wildnames::*, wildnames_cache::*,
+};
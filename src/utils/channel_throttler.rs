@@ -0,0 +1,179 @@
+use crate::{consts::FAR_FUTURE, types::ChannelId, Throttle, ThrottleMode};
+use ahash::HashMap;
+use serde_json::value::RawValue;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tokio::time::Instant;
+
+/// Channel window, which ended with suppressed messages.
+#[derive(Debug)]
+pub(crate) struct SuppressedWindow {
+    pub(crate) channel: ChannelId,
+    pub(crate) count: usize,
+    /// The newest suppressed message in `MergeLatest` mode.
+    pub(crate) latest: Option<Arc<RawValue>>,
+}
+
+/// Fixed window throttling of channels, which match configured patterns.
+///
+/// Windows are flushed lazily with [`ChannelThrottler::take_ended`], so idle windows don't
+/// outlive their interval for long.
+#[derive(Debug, Default)]
+pub(crate) struct ChannelThrottler {
+    pub(crate) throttles: HashMap<ChannelId, Throttle>,
+    windows: Mutex<Windows>,
+}
+
+#[derive(Debug, Default)]
+struct Windows {
+    windows: HashMap<ChannelId, Window>,
+    /// The earliest end of windows, nothing can be taken before it.
+    next_end: Option<Instant>,
+    /// The earliest end of windows with suppressed messages.
+    next_flush: Option<Instant>,
+    /// Time, which flush timer was armed for.
+    timer: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct Window {
+    end: Instant,
+    sent: usize,
+    suppressed: usize,
+    latest: Option<Arc<RawValue>>,
+}
+
+impl ChannelThrottler {
+    #[inline(always)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.throttles.is_empty()
+    }
+
+    #[inline(always)]
+    fn lock(&self) -> MutexGuard<'_, Windows> {
+        self.windows.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Count message sent to `channel`. `patterns` are `channel` and its wildnames.
+    /// Return `true` if message must be suppressed.
+    #[inline]
+    pub(crate) fn check<'a>(
+        &self,
+        channel: &str,
+        mut patterns: impl Iterator<Item = &'a str>,
+        msg: &Arc<RawValue>,
+        now: Instant,
+    ) -> bool {
+        let Some(throttle) = patterns.find_map(|pattern| self.throttles.get(pattern)) else {
+            return false;
+        };
+        let end = now
+            .checked_add(throttle.interval)
+            .unwrap_or_else(|| now + FAR_FUTURE);
+
+        let mut windows = self.lock();
+        let Windows {
+            ref mut windows,
+            ref mut next_end,
+            ref mut next_flush,
+            ..
+        } = *windows;
+
+        let window = windows
+            .entry(channel.to_owned())
+            .or_insert_with(|| Window::new(end));
+        // Ended window with suppressed messages is kept until it's taken.
+        if window.suppressed == 0 && now >= window.end {
+            *window = Window::new(end);
+        }
+        *next_end = Some(next_end.map_or(window.end, |next_end| next_end.min(window.end)));
+
+        if window.sent < throttle.max_messages {
+            window.sent += 1;
+            return false;
+        }
+
+        window.suppressed += 1;
+        if throttle.mode == ThrottleMode::MergeLatest {
+            window.latest = Some(Arc::clone(msg));
+        }
+        *next_flush = Some(next_flush.map_or(window.end, |next_flush| next_flush.min(window.end)));
+
+        true
+    }
+
+    /// Remove windows, which ended before `now`. Return the ones with suppressed messages.
+    #[inline]
+    pub(crate) fn take_ended(&self, now: Instant) -> Vec<SuppressedWindow> {
+        let mut windows = self.lock();
+        if windows.next_end.is_none_or(|next_end| now < next_end) {
+            return Vec::new();
+        }
+
+        let mut ended = Vec::new();
+        let mut next_end = None::<Instant>;
+        let mut next_flush = None::<Instant>;
+        windows.windows.retain(|channel, window| {
+            if window.end <= now {
+                if window.suppressed > 0 {
+                    ended.push(SuppressedWindow {
+                        channel: channel.clone(),
+                        count: window.suppressed,
+                        latest: window.latest.take(),
+                    });
+                }
+                return false;
+            }
+
+            next_end = Some(next_end.map_or(window.end, |next_end| next_end.min(window.end)));
+            if window.suppressed > 0 {
+                next_flush =
+                    Some(next_flush.map_or(window.end, |next_flush| next_flush.min(window.end)));
+            }
+            true
+        });
+        windows.next_end = next_end;
+        windows.next_flush = next_flush;
+
+        ended
+    }
+
+    /// Return time, which flush timer must be armed for,
+    /// if there are suppressed messages and no timer is armed for them yet.
+    #[inline]
+    pub(crate) fn arm_timer(&self) -> Option<Instant> {
+        let mut windows = self.lock();
+        let next_flush = windows.next_flush?;
+        if windows.timer.is_some_and(|timer| timer <= next_flush) {
+            return None;
+        }
+
+        windows.timer = Some(next_flush);
+        Some(next_flush)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn windows_count(&self) -> usize {
+        self.lock().windows.len()
+    }
+
+    /// Mark timer armed for `at` as fired.
+    #[inline]
+    pub(crate) fn disarm_timer(&self, at: Instant) {
+        let mut windows = self.lock();
+        if windows.timer == Some(at) {
+            windows.timer = None;
+        }
+    }
+}
+
+impl Window {
+    #[inline(always)]
+    const fn new(end: Instant) -> Self {
+        Self {
+            end,
+            sent: 0,
+            suppressed: 0,
+            latest: None,
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{types::ClientId, ReconnectAdvice};
+use ahash::HashMap;
+use std::sync::{Mutex, PoisonError};
+use tokio::time::Instant;
+
+/// Reconnect advices of recently evicted clients, so client, which didn't wait
+/// on `/meta/connect` at eviction time, gets advice on the next connect.
+#[derive(Debug, Default)]
+pub(crate) struct EvictedClients {
+    clients: Mutex<HashMap<ClientId, (ReconnectAdvice, Instant)>>,
+}
+
+impl EvictedClients {
+    /// Keep `advice` of evicted client until `expires_at`.
+    #[inline]
+    pub(crate) fn insert(&self, client_id: ClientId, advice: ReconnectAdvice, expires_at: Instant) {
+        let now = Instant::now();
+        let mut clients = self.clients.lock().unwrap_or_else(PoisonError::into_inner);
+
+        clients.retain(|_, &mut (_, expires_at)| now < expires_at);
+        clients.insert(client_id, (advice, expires_at));
+    }
+
+    /// Return advice of evicted client, if it isn't expired yet.
+    #[inline]
+    pub(crate) fn get(&self, client_id: &ClientId) -> Option<ReconnectAdvice> {
+        let now = Instant::now();
+
+        self.clients
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(client_id)
+            .and_then(|&(advice, expires_at)| (now < expires_at).then_some(advice))
+    }
+}
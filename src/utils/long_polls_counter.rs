@@ -0,0 +1,51 @@
+use crate::types::CookieId;
+use ahash::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+/// Count pending long-polls per `BAYEUX_BROWSER`.
+#[derive(Debug, Default)]
+pub(crate) struct LongPollsCounter {
+    counter: Mutex<HashMap<CookieId, usize>>,
+}
+
+impl LongPollsCounter {
+    /// Register new long-poll. Return `None` if browser already have `max` pending long-polls.
+    #[inline]
+    pub(crate) fn acquire(&self, cookie_id: CookieId, max: usize) -> Option<LongPollGuard<'_>> {
+        let mut counter = self.counter.lock().unwrap_or_else(PoisonError::into_inner);
+        let count = counter.entry(cookie_id).or_default();
+
+        if *count < max {
+            *count += 1;
+            Some(LongPollGuard {
+                counter: self,
+                cookie_id,
+            })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn release(&self, cookie_id: &CookieId) {
+        let mut counter = self.counter.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(count) = counter.get_mut(cookie_id) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                counter.remove(cookie_id);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct LongPollGuard<'a> {
+    counter: &'a LongPollsCounter,
+    cookie_id: CookieId,
+}
+
+impl Drop for LongPollGuard<'_> {
+    fn drop(&mut self) {
+        self.counter.release(&self.cookie_id);
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{
+    types::{ClientId, CookieId},
+    RateLimit, ReconnectAdvice,
+};
+use ahash::{HashMap, HashSet};
+use std::sync::{Mutex, PoisonError};
+use tokio::time::Instant;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum RateLimitVerdict {
+    Allowed,
+    Exceeded,
+    Evict(ReconnectAdvice),
+}
+
+/// Per `clientId` and per `BAYEUX_BROWSER` token buckets.
+#[derive(Debug, Default)]
+pub(crate) struct RateLimiter {
+    pub(crate) client_limit: Option<RateLimit>,
+    pub(crate) browser_limit: Option<RateLimit>,
+    pub(crate) eviction: Option<(usize, ReconnectAdvice)>,
+    clients: Mutex<HashMap<ClientId, ClientBuckets>>,
+    browsers: Mutex<HashMap<CookieId, BrowserBuckets>>,
+}
+
+#[derive(Debug)]
+struct ClientBuckets {
+    buckets: Option<Buckets>,
+    violations: usize,
+}
+
+#[derive(Debug)]
+struct BrowserBuckets {
+    buckets: Buckets,
+    client_ids: HashSet<ClientId>,
+}
+
+#[derive(Debug)]
+struct Buckets {
+    messages: Option<TokenBucket>,
+    bytes: Option<TokenBucket>,
+}
+
+impl Buckets {
+    #[inline]
+    fn new(limit: &RateLimit, now: Instant) -> Self {
+        Self {
+            messages: limit
+                .messages_per_second
+                .map(|rate| TokenBucket::new(rate, now)),
+            bytes: limit
+                .bytes_per_second
+                .map(|rate| TokenBucket::new(rate, now)),
+        }
+    }
+
+    #[inline]
+    fn refill_and_check(&mut self, bytes: usize, now: Instant) -> bool {
+        [(&mut self.messages, 1), (&mut self.bytes, bytes)]
+            .into_iter()
+            .all(|(bucket, amount)| {
+                bucket
+                    .as_mut()
+                    .is_none_or(|bucket| bucket.refill_and_check(amount, now))
+            })
+    }
+
+    #[inline]
+    fn take(&mut self, bytes: usize) {
+        if let Some(ref mut bucket) = self.messages {
+            bucket.take(1);
+        }
+        if let Some(ref mut bucket) = self.bytes {
+            bucket.take(bytes);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    #[inline]
+    fn new(rate: u32, now: Instant) -> Self {
+        Self {
+            rate: f64::from(rate),
+            tokens: f64::from(rate),
+            last_refill: now,
+        }
+    }
+
+    #[inline]
+    #[allow(clippy::cast_precision_loss)]
+    fn refill_and_check(&mut self, amount: usize, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = elapsed
+            .as_secs_f64()
+            .mul_add(self.rate, self.tokens)
+            .min(self.rate);
+        self.last_refill = now;
+
+        self.tokens >= amount as f64
+    }
+
+    #[inline]
+    #[allow(clippy::cast_precision_loss)]
+    fn take(&mut self, amount: usize) {
+        self.tokens -= amount as f64;
+    }
+}
+
+impl RateLimiter {
+    /// Violations are tracked if any limit or eviction is configured.
+    #[inline(always)]
+    pub(crate) const fn is_enabled(&self) -> bool {
+        self.client_limit.is_some() || self.browser_limit.is_some() || self.eviction.is_some()
+    }
+
+    /// Return maximum size of message, which can ever pass byte buckets.
+    #[inline]
+    pub(crate) fn max_message_size(&self) -> Option<usize> {
+        [self.client_limit, self.browser_limit]
+            .into_iter()
+            .flatten()
+            .filter_map(|limit| limit.bytes_per_second)
+            .min()
+            .map(|bytes| bytes as usize)
+    }
+
+    /// Account one message with `bytes` size.
+    pub(crate) fn check(
+        &self,
+        client_id: ClientId,
+        cookie_id: CookieId,
+        bytes: usize,
+    ) -> RateLimitVerdict {
+        if !self.is_enabled() {
+            return RateLimitVerdict::Allowed;
+        }
+
+        let now = Instant::now();
+        let mut clients = self.clients.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut browsers = self.browsers.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let client = self.client_entry(&mut clients, client_id, now);
+        let mut browser = self.browser_limit.as_ref().map(|limit| {
+            let browser = browsers.entry(cookie_id).or_insert_with(|| BrowserBuckets {
+                buckets: Buckets::new(limit, now),
+                client_ids: Default::default(),
+            });
+            browser.client_ids.insert(client_id);
+            browser
+        });
+
+        let client_allowed = client
+            .buckets
+            .as_mut()
+            .is_none_or(|buckets| buckets.refill_and_check(bytes, now));
+        let browser_allowed = browser
+            .as_mut()
+            .is_none_or(|browser| browser.buckets.refill_and_check(bytes, now));
+
+        if client_allowed && browser_allowed {
+            if let Some(ref mut buckets) = client.buckets {
+                buckets.take(bytes);
+            }
+            if let Some(browser) = browser {
+                browser.buckets.take(bytes);
+            }
+            client.violations = 0;
+
+            RateLimitVerdict::Allowed
+        } else {
+            self.violation(client)
+        }
+    }
+
+    /// Account violation which was detected outside of `check` (e.g. too big batch).
+    pub(crate) fn report_violation(&self, client_id: ClientId) -> RateLimitVerdict {
+        if self.eviction.is_none() {
+            return RateLimitVerdict::Exceeded;
+        }
+
+        let now = Instant::now();
+        let mut clients = self.clients.lock().unwrap_or_else(PoisonError::into_inner);
+        let client = self.client_entry(&mut clients, client_id, now);
+
+        self.violation(client)
+    }
+
+    #[inline]
+    fn client_entry<'a>(
+        &self,
+        clients: &'a mut HashMap<ClientId, ClientBuckets>,
+        client_id: ClientId,
+        now: Instant,
+    ) -> &'a mut ClientBuckets {
+        clients.entry(client_id).or_insert_with(|| ClientBuckets {
+            buckets: self
+                .client_limit
+                .as_ref()
+                .map(|limit| Buckets::new(limit, now)),
+            violations: 0,
+        })
+    }
+
+    #[inline]
+    const fn violation(&self, client: &mut ClientBuckets) -> RateLimitVerdict {
+        client.violations += 1;
+
+        match self.eviction {
+            Some((max_violations, advice)) if client.violations >= max_violations => {
+                RateLimitVerdict::Evict(advice)
+            }
+            _ => RateLimitVerdict::Exceeded,
+        }
+    }
+
+    /// Remove buckets of removed client.
+    #[inline]
+    pub(crate) fn remove(&self, client_id: &ClientId, cookie_id: &CookieId) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        self.clients
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(client_id);
+
+        let mut browsers = self.browsers.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(browser) = browsers.get_mut(cookie_id) {
+            browser.client_ids.remove(client_id);
+            if browser.client_ids.is_empty() {
+                browsers.remove(cookie_id);
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{ScheduleHandle, ScheduledMessage};
+use ahash::HashMap;
+use core::sync::atomic::{AtomicU64, Ordering};
+use serde_json::value::RawValue;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tokio::{task::AbortHandle, time::Instant};
+
+/// Timer subsystem of delayed messages.
+/// Every message is sent by its own task, tasks are aborted when scheduler is dropped.
+#[derive(Debug, Default)]
+pub(crate) struct Scheduler {
+    next_id: AtomicU64,
+    entries: Arc<ScheduledEntries>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ScheduledEntries(Mutex<HashMap<u64, ScheduledEntry>>);
+
+#[derive(Debug)]
+pub(crate) struct ScheduledEntry {
+    pub(crate) channel: Arc<str>,
+    pub(crate) msg: Arc<RawValue>,
+    send_at: Instant,
+    abort: AbortHandle,
+}
+
+impl Scheduler {
+    /// Register message. `spawn` gets message id and must spawn task, which sends it.
+    #[inline]
+    pub(crate) fn schedule(
+        &self,
+        channel: &str,
+        msg: Arc<RawValue>,
+        send_at: Instant,
+        spawn: impl FnOnce(u64) -> AbortHandle,
+    ) -> ScheduleHandle {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        // Task is spawned under lock, so it can't take entry before it was inserted.
+        let mut entries = self.entries.lock();
+        entries.insert(
+            id,
+            ScheduledEntry {
+                channel: Arc::from(channel),
+                msg,
+                send_at,
+                abort: spawn(id),
+            },
+        );
+
+        ScheduleHandle::new(id, Arc::downgrade(&self.entries))
+    }
+
+    /// Take message, which must be sent now.
+    #[inline]
+    pub(crate) fn take(&self, id: u64) -> Option<ScheduledEntry> {
+        self.entries.lock().remove(&id)
+    }
+
+    /// List pending messages ordered by send time.
+    #[inline]
+    pub(crate) fn list(&self) -> Vec<ScheduledMessage> {
+        let mut messages = self
+            .entries
+            .lock()
+            .iter()
+            .map(|(&id, entry)| ScheduledMessage {
+                id,
+                channel: entry.channel.to_string(),
+                send_at: entry.send_at,
+            })
+            .collect::<Vec<_>>();
+        messages.sort_by_key(|message| (message.send_at, message.id));
+
+        messages
+    }
+}
+
+impl Drop for Scheduler {
+    fn drop(&mut self) {
+        for entry in self.entries.lock().values() {
+            entry.abort.abort();
+        }
+    }
+}
+
+impl ScheduledEntries {
+    #[inline(always)]
+    fn lock(&self) -> MutexGuard<'_, HashMap<u64, ScheduledEntry>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Remove message and abort its task.
+    /// Return `false` if message was already sent or removed.
+    #[inline]
+    pub(crate) fn remove(&self, id: u64) -> bool {
+        let Some(entry) = self.lock().remove(&id) else {
+            return false;
+        };
+        entry.abort.abort();
+
+        true
+    }
+
+    #[inline]
+    pub(crate) fn contains(&self, id: u64) -> bool {
+        self.lock().contains_key(&id)
+    }
+}
@@ -1,3 +1,5 @@
+mod test_channel_name_rules;
+mod test_channel_throttler;
 mod test_get_wildnames;
 mod test_send_channel_name_validation;
 mod test_subscribe_channel_name_validation;
@@ -0,0 +1,65 @@
+use crate::{ChannelNameRules, ChannelNameValidator as _};
+
+#[test]
+fn test_allowed_char() {
+    let validator = ChannelNameRules::new();
+    assert!(!validator.validate_send_channel_name("/users/jöhn.doe"));
+
+    let validator = ChannelNameRules::new().allowed_char(|c| c.is_alphanumeric() || c == '.');
+    assert!(validator.validate_send_channel_name("/users/jöhn.doe"));
+    assert!(validator.validate_subscribe_channel_name("/users/jöhn.doe/*"));
+    assert!(!validator.validate_send_channel_name("/users/john-doe"));
+}
+
+#[test]
+fn test_max_depth() {
+    let validator = ChannelNameRules::new().max_depth(2);
+
+    assert!(validator.validate_send_channel_name("/first/second/"));
+    assert!(validator.validate_subscribe_channel_name("/first/second/**"));
+    assert!(!validator.validate_send_channel_name("/first/second/third"));
+}
+
+#[test]
+fn test_max_length() {
+    let validator = ChannelNameRules::new().max_length(6);
+
+    assert!(validator.validate_send_channel_name("/first"));
+    assert!(!validator.validate_send_channel_name("/first/"));
+}
+
+#[test]
+fn test_reserved_prefix() {
+    let validator = ChannelNameRules::new().reserved_prefix("/service/");
+
+    assert!(validator.validate_send_channel_name("/services"));
+    assert!(!validator.validate_send_channel_name("/service/chat"));
+    assert!(!validator.validate_subscribe_channel_name("/service/**"));
+
+    // Wildcards, which match reserved channels.
+    assert!(!validator.validate_subscribe_channel_name("/**"));
+    assert!(validator.validate_subscribe_channel_name("/*"));
+    assert!(validator.validate_subscribe_channel_name("/chat/**"));
+
+    let validator = ChannelNameRules::new().reserved_prefix("/serv");
+    assert!(!validator.validate_subscribe_channel_name("/*"));
+}
+
+#[test]
+fn test_allowed_char_closure() {
+    let extra = String::from(".:");
+    let validator = ChannelNameRules::new()
+        .allowed_char(move |c| c.is_ascii_alphanumeric() || extra.contains(c));
+
+    assert!(validator.validate_send_channel_name("/chat.room:1"));
+    assert!(!validator.validate_send_channel_name("/chat-room"));
+}
+
+#[test]
+fn test_wildcards() {
+    let validator = ChannelNameRules::new().wildcards(false);
+
+    assert!(validator.validate_subscribe_channel_name("/first/second"));
+    assert!(!validator.validate_subscribe_channel_name("/first/*"));
+    assert!(!validator.validate_subscribe_channel_name("/**"));
+}
@@ -0,0 +1,62 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{utils::ChannelThrottler, Throttle};
+use core::time::Duration;
+use serde_json::value::RawValue;
+use std::sync::Arc;
+use tokio::time::Instant;
+
+const INTERVAL: Duration = Duration::from_secs(1);
+
+fn throttler() -> ChannelThrottler {
+    let mut throttler = ChannelThrottler::default();
+    throttler
+        .throttles
+        .insert("/prices/*".to_owned(), Throttle::new(1, INTERVAL));
+
+    throttler
+}
+
+fn check(throttler: &ChannelThrottler, channel: &str, now: Instant) -> bool {
+    let msg = Arc::from(RawValue::from_string("0".to_owned()).unwrap());
+
+    throttler.check(channel, [channel, "/prices/*"].into_iter(), &msg, now)
+}
+
+#[test]
+fn test_idle_windows_are_evicted() {
+    let throttler = throttler();
+    let now = Instant::now();
+
+    for i in 0..10 {
+        assert!(!check(&throttler, &format!("/prices/{i}"), now));
+    }
+    assert!(check(&throttler, "/prices/0", now));
+    assert_eq!(throttler.windows_count(), 10);
+
+    assert!(throttler.take_ended(now + INTERVAL / 2).is_empty());
+    assert_eq!(throttler.windows_count(), 10);
+
+    let ended = throttler.take_ended(now + INTERVAL);
+    assert_eq!(throttler.windows_count(), 0);
+    assert_eq!(ended.len(), 1);
+    let ended = ended.first().unwrap();
+    assert_eq!((&*ended.channel, ended.count), ("/prices/0", 1));
+}
+
+#[test]
+fn test_arm_timer() {
+    let throttler = throttler();
+    let now = Instant::now();
+
+    assert_eq!(throttler.arm_timer(), None);
+
+    assert!(!check(&throttler, "/prices/0", now));
+    assert!(check(&throttler, "/prices/0", now));
+    assert_eq!(throttler.arm_timer(), Some(now + INTERVAL));
+    assert_eq!(throttler.arm_timer(), None);
+
+    throttler.disarm_timer(now + INTERVAL);
+    let _ = throttler.take_ended(now + INTERVAL);
+    assert_eq!(throttler.arm_timer(), None);
+}
@@ -1,9 +1,9 @@
-use crate::utils::{get_wild_names, ChannelNameValidator};
+use crate::{utils::get_wild_names, ChannelNameRules, ChannelNameValidator as _};
 use test_common::TEST_WILDNAMES;
 
 #[test]
 fn test_get_wildnames() {
-    let validator = ChannelNameValidator::default();
+    let validator = ChannelNameRules::default();
 
     for (channel, result) in TEST_WILDNAMES {
         let ret = validator
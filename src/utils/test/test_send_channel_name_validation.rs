@@ -1,9 +1,9 @@
-use crate::utils::ChannelNameValidator;
+use crate::{ChannelNameRules, ChannelNameValidator as _};
 use test_common::TEST_SEND_CHANNEL_NAMES;
 
 #[test]
 fn test_send_channel_name_validator() {
-    let validator = ChannelNameValidator::default();
+    let validator = ChannelNameRules::default();
 
     for (channel, result) in TEST_SEND_CHANNEL_NAMES {
         assert_eq!(
@@ -0,0 +1,42 @@
+use crate::types::ClientId;
+use ahash::{HashMap, HashSet};
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// Index of application users to their sessions.
+#[derive(Debug, Default)]
+pub(crate) struct UsersIndex {
+    users: RwLock<HashMap<Arc<str>, HashSet<ClientId>>>,
+}
+
+impl UsersIndex {
+    #[inline]
+    pub(crate) fn insert(&self, user: Arc<str>, client_id: ClientId) {
+        self.users
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(user)
+            .or_default()
+            .insert(client_id);
+    }
+
+    #[inline]
+    pub(crate) fn remove(&self, user: &str, client_id: &ClientId) {
+        let mut users = self.users.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(client_ids) = users.get_mut(user) {
+            client_ids.remove(client_id);
+            if client_ids.is_empty() {
+                users.remove(user);
+            }
+        }
+    }
+
+    #[inline]
+    pub(crate) fn client_ids(&self, user: &str) -> Vec<ClientId> {
+        self.users
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(user)
+            .map(|client_ids| client_ids.iter().copied().collect())
+            .unwrap_or_default()
+    }
+}
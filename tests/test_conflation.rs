@@ -0,0 +1,152 @@
+use axum_cometd::{LongPollingServiceContext, LongPollingServiceContextBuilder, RouterBuilder};
+use core::time::Duration;
+use serde_json::json;
+use std::sync::Arc;
+use test_common::ClientMock;
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+fn build_mock_client(context: &Arc<LongPollingServiceContext<(), ()>>) -> ClientMock {
+    let router = RouterBuilder::new().build::<()>(Arc::clone(context));
+
+    ClientMock::create("", "/", "", "", router)
+}
+
+#[tokio::test]
+async fn test_conflate_channel() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .conflate("/prices/*")
+        .build();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client
+        .subscribe(&["/prices/*", "/news"])
+        .await
+        .unwrap();
+
+    for i in 0..5 {
+        context.send("/prices/AAPL", json!(i)).await.unwrap();
+    }
+    context.send("/news", json!(0)).await.unwrap();
+    context.send("/news", json!(1)).await.unwrap();
+    context.send("/prices/MSFT", json!(0)).await.unwrap();
+
+    let mut responses = Vec::new();
+    for _ in 0..4 {
+        responses.extend(mock_client.connect().await);
+    }
+    assert_eq!(
+        responses,
+        [
+            ("/prices/AAPL".to_owned(), json!(4)),
+            ("/news".to_owned(), json!(0)),
+            ("/news".to_owned(), json!(1)),
+            ("/prices/MSFT".to_owned(), json!(0)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_conflate_by_key() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .conflate_by("/prices", |data| data["symbol"].as_str().map(str::to_owned))
+        .build();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/prices"]).await.unwrap();
+
+    for data in [
+        json!({"symbol": "AAPL", "value": 1}),
+        json!({"symbol": "MSFT", "value": 1}),
+        json!({"value": 1}),
+        json!({"symbol": "AAPL", "value": 2}),
+        json!({"value": 2}),
+    ] {
+        context.send("/prices", data).await.unwrap();
+    }
+
+    let mut responses = Vec::new();
+    for _ in 0..4 {
+        responses.extend(
+            mock_client
+                .connect()
+                .await
+                .into_iter()
+                .map(|(_, data)| data),
+        );
+    }
+    assert_eq!(
+        responses,
+        [
+            json!({"symbol": "MSFT", "value": 1}),
+            json!({"value": 1}),
+            json!({"symbol": "AAPL", "value": 2}),
+            json!({"value": 2}),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_conflation_frees_queue() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .client_channel_capacity(2)
+        .conflate("/prices")
+        .build();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/prices"]).await.unwrap();
+
+    context.send("/prices", json!(0)).await.unwrap();
+    context.send("/prices", json!(1)).await.unwrap();
+    assert_eq!(
+        mock_client.connect().await,
+        [("/prices".to_owned(), json!(1))]
+    );
+
+    for i in 2..4 {
+        let report = context.send("/prices", json!(i)).await.unwrap();
+        assert_eq!(report.blocked, 0);
+    }
+    assert_eq!(
+        mock_client.connect().await,
+        [("/prices".to_owned(), json!(3))]
+    );
+}
+
+#[tokio::test]
+async fn test_conflation_doesnt_fill_queue() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .client_channel_capacity(2)
+        .conflate("/prices")
+        .build();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/prices", "/news"]).await.unwrap();
+
+    for i in 0..10 {
+        let report = context.send("/prices", json!(i)).await.unwrap();
+        assert_eq!((report.recipients, report.blocked), (1, 0));
+    }
+    let report = context.send("/news", json!(0)).await.unwrap();
+    assert_eq!((report.recipients, report.blocked), (1, 0));
+
+    let mut responses = Vec::new();
+    for _ in 0..2 {
+        responses.extend(mock_client.connect().await);
+    }
+    assert_eq!(
+        responses,
+        [
+            ("/prices".to_owned(), json!(9)),
+            ("/news".to_owned(), json!(0)),
+        ]
+    );
+}
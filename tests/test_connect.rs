@@ -1,10 +1,14 @@
-use axum::http::StatusCode;
+use axum::{
+    body::Body,
+    http::{header::CONTENT_TYPE, Request, StatusCode},
+};
 use axum_cometd::{LongPollingServiceContextBuilder, RouterBuilder};
 use core::time::Duration;
 use serde_json::json;
 use std::sync::Arc;
 use test_common::{ClientMock, ResponseExt as _, TEST_CLIENT_ID};
 use tokio::join;
+use tower::ServiceExt as _;
 
 const TIMEOUT: Duration = Duration::from_secs(1);
 
@@ -33,6 +37,30 @@ async fn test_wrong_channel() {
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
 
+#[tokio::test]
+async fn test_wrong_content_type() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .build::<(), ()>();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    let body = json!([{"channel": "/meta/connect"}]).to_string();
+
+    for content_type in [None, Some("text/plain")] {
+        let request = Request::builder().uri("/connect").method("POST");
+        let request = match content_type {
+            Some(content_type) => request.header(CONTENT_TYPE, content_type),
+            None => request,
+        };
+        let response = router
+            .clone()
+            .oneshot(request.body(Body::from(body.clone())).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+}
+
 #[tokio::test]
 async fn test_empty_client_id() {
     let mock_client = build_mock_client();
@@ -154,7 +182,18 @@ async fn test_channel_was_closed() {
                 .await
         }
     );
-    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.to_json().await,
+        json!([{
+            "id": id,
+            "channel": "/meta/connect",
+            "successful": true,
+            "advice": {
+                "reconnect": "none"
+            },
+        }])
+    );
 }
 
 #[tokio::test]
@@ -207,3 +246,75 @@ async fn test_double_connect_same_client_id() {
         }])
     );
 }
+
+async fn connect(mock_client: &ClientMock) -> (String, serde_json::Value) {
+    let id = mock_client.next_id();
+    let response = mock_client
+        .send_request(
+            mock_client.connect_endpoint(),
+            json!([{
+              "id": id,
+              "channel": "/meta/connect",
+              "connectionType": "long-polling",
+              "clientId": mock_client.client_id(),
+            }]),
+        )
+        .await
+        .to_json()
+        .await;
+
+    (id, response)
+}
+
+#[tokio::test]
+async fn test_max_sessions_per_browser() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .max_sessions_per_browser(1)
+        .multiple_clients_interval(Duration::from_millis(2000))
+        .build();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    // Both clients use the same `BAYEUX_BROWSER` cookie.
+    let mut mock_client0 = ClientMock::create("", "/", "", "", router.clone());
+    let mut mock_client1 = ClientMock::create("", "/", "", "", router);
+    mock_client0.handshake().await;
+    mock_client1.handshake().await;
+
+    let ((id0, resp0), (id1, resp1)) = join!(connect(&mock_client0), async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        connect(&mock_client1).await
+    });
+
+    assert_eq!(
+        resp0,
+        json!([{
+            "id": id0,
+            "channel": "/meta/connect",
+            "successful": true,
+            "advice": {
+                "interval": 0,
+                "reconnect": "retry",
+                "timeout": TIMEOUT.as_millis() as u64
+            },
+        }])
+    );
+    assert_eq!(
+        resp1,
+        json!([{
+            "id": id1,
+            "channel": "/meta/connect",
+            "successful": true,
+            "advice": {
+                "interval": 2000,
+                "multiple-clients": true,
+                "reconnect": "retry",
+                "timeout": TIMEOUT.as_millis() as u64
+            },
+        }])
+    );
+
+    // Slot is released after first connect was finished.
+    let (id1, resp1) = connect(&mock_client1).await;
+    assert_eq!(resp1[0]["id"], id1);
+    assert_eq!(resp1[0]["advice"].get("multiple-clients"), None);
+}
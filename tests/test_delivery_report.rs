@@ -0,0 +1,115 @@
+use axum_cometd::{
+    DeliveryReport, LongPollingServiceContext, LongPollingServiceContextBuilder, RouterBuilder,
+};
+use core::time::Duration;
+use serde_json::json;
+use std::sync::Arc;
+use test_common::ClientMock;
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+fn build_context(client_channel_capacity: usize) -> Arc<LongPollingServiceContext<(), ()>> {
+    LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .client_channel_capacity(client_channel_capacity)
+        .build()
+}
+
+fn build_mock_client(context: &Arc<LongPollingServiceContext<(), ()>>) -> ClientMock {
+    let router = RouterBuilder::new().build::<()>(Arc::clone(context));
+
+    ClientMock::create("", "/", "", "", router)
+}
+
+#[tokio::test]
+async fn test_no_subscribers() {
+    let context = build_context(10);
+
+    assert!(!context.has_subscribers("/foo/bar").await);
+
+    let report = context.send("/foo/bar", json!("Hello")).await.unwrap();
+    assert_eq!(report, DeliveryReport::default());
+    assert!(report.is_empty());
+}
+
+#[tokio::test]
+async fn test_delivery_report() {
+    let context = build_context(10);
+    let mut mock_client0 = build_mock_client(&context);
+    let mut mock_client1 = build_mock_client(&context);
+
+    mock_client0.handshake().await;
+    mock_client1.handshake().await;
+    mock_client0
+        .subscribe(&["/foo/bar", "/foo/**"])
+        .await
+        .unwrap();
+    mock_client1.subscribe(&["/**"]).await.unwrap();
+
+    assert!(context.has_subscribers("/foo/bar").await);
+    assert!(context.has_subscribers("/baz").await);
+
+    let mut report = context.send("/foo/bar", json!("Hello")).await.unwrap();
+    report.channels.sort();
+    assert_eq!(
+        report,
+        DeliveryReport {
+            channels: vec![
+                "/**".to_owned(),
+                "/foo/**".to_owned(),
+                "/foo/bar".to_owned()
+            ],
+            recipients: 2,
+            dropped: 0,
+            blocked: 0,
+            throttled: false,
+        }
+    );
+
+    mock_client1.disconnect().await;
+    assert!(!context.has_subscribers("/baz").await);
+
+    let report = context.send("/baz", json!("Hello")).await.unwrap();
+    assert!(report.is_empty());
+    assert!(report.channels.is_empty());
+}
+
+#[tokio::test]
+async fn test_stalled_subscriber_doesnt_block() {
+    let context = build_context(1);
+    let mut stalled_client = build_mock_client(&context);
+    let mut mock_client = build_mock_client(&context);
+
+    stalled_client.handshake().await;
+    mock_client.handshake().await;
+    stalled_client.subscribe(&["/**"]).await.unwrap();
+    mock_client.subscribe(&["/topic"]).await.unwrap();
+
+    let report = context.send("/topic", json!(0)).await.unwrap();
+    assert_eq!(
+        (report.recipients, report.dropped, report.blocked),
+        (2, 0, 0)
+    );
+    assert_eq!(
+        mock_client.connect().await,
+        [("/topic".to_owned(), json!(0))]
+    );
+
+    // Queue of stalled client is full, message is dropped for it without waiting.
+    let report = tokio::time::timeout(TIMEOUT / 10, context.send("/topic", json!(1)))
+        .await
+        .expect("send was blocked by stalled subscriber")
+        .unwrap();
+    assert_eq!(
+        (report.recipients, report.dropped, report.blocked),
+        (1, 1, 1)
+    );
+    assert_eq!(
+        mock_client.connect().await,
+        [("/topic".to_owned(), json!(1))]
+    );
+    assert_eq!(
+        stalled_client.connect().await,
+        [("/topic".to_owned(), json!(0))]
+    );
+}
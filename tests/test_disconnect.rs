@@ -1,18 +1,43 @@
-use axum_cometd::{LongPollingServiceContextBuilder, RouterBuilder};
+use axum_cometd::{LongPollingServiceContext, LongPollingServiceContextBuilder, RouterBuilder};
 use core::time::Duration;
-use serde_json::json;
+use serde_json::{json, Value as JsonValue};
 use std::sync::Arc;
 use test_common::{ClientMock, ResponseExt};
+use tokio::join;
 
 const TIMEOUT: Duration = Duration::from_secs(1);
 
-fn build_mock_client() -> ClientMock {
+fn build_context_and_mock_client() -> (Arc<LongPollingServiceContext<(), ()>>, ClientMock) {
     let context = LongPollingServiceContextBuilder::new()
         .timeout(TIMEOUT)
         .build();
     let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    let mock_client = ClientMock::create("", "/", "", "", router);
 
-    ClientMock::create("", "/", "", "", router)
+    (context, mock_client)
+}
+
+fn build_mock_client() -> ClientMock {
+    build_context_and_mock_client().1
+}
+
+async fn pending_connect(mock_client: &ClientMock, id: &str) -> JsonValue {
+    tokio::time::timeout(
+        TIMEOUT / 2,
+        mock_client.send_request(
+            mock_client.connect_endpoint(),
+            json!([{
+              "id": id,
+              "channel": "/meta/connect",
+              "connectionType": "long-polling",
+              "clientId": mock_client.client_id(),
+            }]),
+        ),
+    )
+    .await
+    .expect("pending connect wasn't woken up")
+    .to_json()
+    .await
 }
 
 #[tokio::test]
@@ -89,3 +114,61 @@ async fn test_missing_client_id() {
         }])
     );
 }
+
+#[tokio::test]
+async fn test_disconnect_wakes_pending_connect() {
+    let (_context, mut mock_client) = build_context_and_mock_client();
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/FOO_BAR"]).await.unwrap();
+
+    let id = mock_client.next_id();
+    let (response, ()) = join!(pending_connect(&mock_client, &id), async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        mock_client.disconnect().await
+    });
+
+    assert_eq!(
+        response,
+        json!([{
+            "id": id,
+            "channel": "/meta/connect",
+            "successful": true,
+            "advice": {
+                "reconnect": "none"
+            },
+        }])
+    );
+}
+
+#[tokio::test]
+async fn test_remove_wakes_pending_connect() {
+    let (context, mut mock_client) = build_context_and_mock_client();
+    let mut rx = context.rx();
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/FOO_BAR"]).await.unwrap();
+
+    let client_id = match *rx.recv().await.unwrap() {
+        axum_cometd::Event::SessionAdded { client_id, .. } => client_id,
+        ref event => panic!("unexpected event: {event:?}"),
+    };
+
+    let id = mock_client.next_id();
+    let (response, ()) = join!(pending_connect(&mock_client, &id), async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        context.unsubscribe(client_id).await
+    });
+
+    assert_eq!(
+        response,
+        json!([{
+            "id": id,
+            "channel": "/meta/connect",
+            "successful": false,
+            "error": "402::session_unknown",
+            "advice": {
+                "interval": 0,
+                "reconnect": "handshake"
+            },
+        }])
+    );
+}
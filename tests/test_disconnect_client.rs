@@ -0,0 +1,192 @@
+use axum_cometd::{
+    ClientId, Event, LongPollingServiceContext, LongPollingServiceContextBuilder, ReconnectAdvice,
+    RouterBuilder,
+};
+use core::time::Duration;
+use serde_json::{json, Value as JsonValue};
+use std::sync::Arc;
+use test_common::{ClientMock, ResponseExt as _, TEST_CLIENT_ID};
+use tokio::join;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn build_context_and_mock_client() -> (Arc<LongPollingServiceContext<(), ()>>, ClientMock) {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .build();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    let mock_client = ClientMock::create("", "/", "", "", router);
+
+    (context, mock_client)
+}
+
+fn parse_client_id(client_id: &str) -> ClientId {
+    serde_json::from_value(json!(client_id)).unwrap()
+}
+
+async fn connect_and_evict(
+    context: &Arc<LongPollingServiceContext<(), ()>>,
+    mock_client: &ClientMock,
+    advice: ReconnectAdvice,
+) -> (String, JsonValue) {
+    let client_id = parse_client_id(mock_client.client_id().unwrap());
+
+    let id = mock_client.next_id();
+    let (response, evicted) = join!(
+        async {
+            mock_client
+                .send_request(
+                    mock_client.connect_endpoint(),
+                    json!([{
+                      "id": id,
+                      "channel": "/meta/connect",
+                      "connectionType": "long-polling",
+                      "clientId": mock_client.client_id(),
+                    }]),
+                )
+                .await
+                .to_json()
+                .await
+        },
+        async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            context.disconnect_client(client_id, advice).await
+        }
+    );
+    assert!(evicted);
+
+    (id, response)
+}
+
+#[tokio::test]
+async fn test_disconnect_client_none() {
+    let (context, mut mock_client) = build_context_and_mock_client();
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/FOO_BAR"]).await.unwrap();
+
+    let (id, response) = tokio::time::timeout(
+        TIMEOUT / 2,
+        connect_and_evict(&context, &mock_client, ReconnectAdvice::None),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        response,
+        json!([{
+            "id": id,
+            "channel": "/meta/connect",
+            "successful": true,
+            "advice": {
+                "reconnect": "none"
+            },
+        }])
+    );
+
+    let json_body = mock_client.subscribe(&["/FOO_BAR"]).await.unwrap_err();
+    assert_eq!(json_body[0]["error"], "402::session_unknown");
+}
+
+#[tokio::test]
+async fn test_disconnect_client_retry() {
+    let (context, mut mock_client) = build_context_and_mock_client();
+    mock_client.handshake().await;
+
+    let (id, response) = connect_and_evict(
+        &context,
+        &mock_client,
+        ReconnectAdvice::Retry(Duration::from_secs(30)),
+    )
+    .await;
+
+    assert_eq!(
+        response,
+        json!([{
+            "id": id,
+            "channel": "/meta/connect",
+            "successful": true,
+            "advice": {
+                "interval": 30_000,
+                "reconnect": "retry"
+            },
+        }])
+    );
+}
+
+#[tokio::test]
+async fn test_disconnect_client_between_connects() {
+    let (context, mut mock_client) = build_context_and_mock_client();
+    mock_client.handshake().await;
+    let client_id = parse_client_id(mock_client.client_id().unwrap());
+
+    assert!(
+        context
+            .disconnect_client(client_id, ReconnectAdvice::None)
+            .await
+    );
+
+    let id = mock_client.next_id();
+    let response = mock_client
+        .send_request(
+            mock_client.connect_endpoint(),
+            json!([{
+              "id": id,
+              "channel": "/meta/connect",
+              "connectionType": "long-polling",
+              "clientId": mock_client.client_id(),
+            }]),
+        )
+        .await
+        .to_json()
+        .await;
+
+    assert_eq!(
+        response,
+        json!([{
+            "id": id,
+            "channel": "/meta/connect",
+            "successful": true,
+            "advice": {
+                "reconnect": "none"
+            },
+        }])
+    );
+}
+
+#[tokio::test]
+async fn test_disconnect_client_events() {
+    let (context, mut mock_client) = build_context_and_mock_client();
+    let mut rx = context.rx();
+    mock_client.handshake().await;
+    let client_id = parse_client_id(mock_client.client_id().unwrap());
+
+    assert!(
+        context
+            .disconnect_client(client_id, ReconnectAdvice::Handshake)
+            .await
+    );
+
+    assert!(matches!(
+        *rx.recv().await.unwrap(),
+        Event::SessionAdded { client_id: id, .. } if id == client_id
+    ));
+    assert!(matches!(
+        *rx.recv().await.unwrap(),
+        Event::SessionEvicted { client_id: id, advice: ReconnectAdvice::Handshake } if id == client_id
+    ));
+    assert!(matches!(
+        *rx.recv().await.unwrap(),
+        Event::SessionRemoved { client_id: id, .. } if id == client_id
+    ));
+}
+
+#[tokio::test]
+async fn test_disconnect_unknown_client() {
+    let (context, _) = build_context_and_mock_client();
+
+    assert!(
+        !context
+            .disconnect_client(parse_client_id(TEST_CLIENT_ID), ReconnectAdvice::None)
+            .await
+    );
+}
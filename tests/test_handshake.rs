@@ -1,4 +1,5 @@
-use axum_cometd::{LongPollingServiceContextBuilder, RouterBuilder};
+use axum::http::header::SET_COOKIE;
+use axum_cometd::{LongPollingServiceContextBuilder, NodeIdGenerator, RouterBuilder, SameSite};
 use core::time::Duration;
 use serde_json::json;
 use std::sync::Arc;
@@ -61,3 +62,64 @@ async fn test_wrong_channel() {
         }])
     );
 }
+
+#[tokio::test]
+async fn test_cookie_attributes() {
+    // Values loaded at runtime.
+    let domain = String::from("example.com");
+
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .cookie_name("MY_BROWSER")
+        .cookie_path("/notifications")
+        .cookie_domain(domain)
+        .cookie_secure(true)
+        .cookie_http_only(true)
+        .cookie_same_site(SameSite::None)
+        .cookie_max_age(Duration::from_secs(60 * 60))
+        .build();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    let mock_client = ClientMock::create("", "/", "", "", router);
+
+    let response = mock_client
+        .send_request(
+            mock_client.handshake_endpoint(),
+            json!([{
+              "id": mock_client.next_id(),
+              "version": "1.0",
+              "minimumVersion": "1.0",
+              "channel": "/meta/handshake",
+              "supportedConnectionTypes": [ "long-polling" ],
+            }]),
+        )
+        .await;
+
+    let cookie = response.headers()[SET_COOKIE].to_str().unwrap();
+    assert!(cookie.starts_with("MY_BROWSER="), "{cookie}");
+    for attribute in [
+        "Path=/notifications",
+        "Domain=example.com",
+        "Secure",
+        "HttpOnly",
+        "SameSite=None",
+        "Max-Age=3600",
+    ] {
+        assert!(cookie.contains(attribute), "{cookie}");
+    }
+}
+
+#[tokio::test]
+async fn test_node_id_generator() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .id_generator(NodeIdGenerator::new(0xABCD))
+        .build();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    let mut mock_client = ClientMock::create("", "/", "", "", router);
+
+    mock_client.handshake().await;
+
+    let client_id = mock_client.client_id().unwrap();
+    assert_eq!(client_id.len(), 40, "{client_id}");
+    assert!(client_id.starts_with("0000abcd"), "{client_id}");
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "json-schema")]
+
+use axum_cometd::{LongPollingServiceContextBuilder, RouterBuilder, TypedChannel};
+use core::time::Duration;
+use serde_json::{json, Value as JsonValue};
+use std::sync::Arc;
+use test_common::{ClientMock, ResponseExt as _};
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+async fn publish(mock_client: &ClientMock, channel: &str, data: JsonValue) -> JsonValue {
+    let body = json!([{
+      "id": mock_client.next_id(),
+      "channel": channel,
+      "data": data,
+      "clientId": mock_client.client_id(),
+    }]);
+
+    mock_client
+        .send_request(mock_client.connect_endpoint(), body)
+        .await
+        .to::<[JsonValue; 1]>()
+        .await
+        .into_iter()
+        .next()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_json_schema() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .json_schema(
+            "/prices/*",
+            &json!({
+                "type": "object",
+                "properties": { "value": { "type": "number" } },
+                "required": ["value"],
+            }),
+        )
+        .unwrap()
+        .json_schema("/prices/abc", &json!({ "required": ["symbol"] }))
+        .unwrap()
+        .build::<(), ()>();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    let mut mock_client = ClientMock::create("", "/", "", "", router);
+    mock_client.handshake().await;
+
+    let response = publish(&mock_client, "/prices/def", json!({"value": 1})).await;
+    assert_eq!(response["successful"], json!(true));
+
+    let response = publish(&mock_client, "/prices/def", json!({"value": "1"})).await;
+    assert_eq!(response["successful"], json!(false));
+    assert!(response["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("400:/value:"));
+
+    let response = publish(&mock_client, "/prices/abc", json!({"value": 1})).await;
+    assert_eq!(response["successful"], json!(false));
+    assert!(response["error"].as_str().unwrap().starts_with("400::"));
+
+    let response = publish(
+        &mock_client,
+        "/prices/abc",
+        json!({"symbol": "ABC", "value": 1}),
+    )
+    .await;
+    assert_eq!(response["successful"], json!(true));
+
+    let response = publish(&mock_client, "/news", json!("Hello")).await;
+    assert_eq!(response["successful"], json!(true));
+}
+
+#[tokio::test]
+async fn test_json_schema_with_typed_channel() {
+    const PRICES: TypedChannel<JsonValue> = TypedChannel::new("/prices");
+
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .json_schema("/prices", &json!({ "required": ["value"] }))
+        .unwrap()
+        .typed_channel(&PRICES)
+        .json_schema("/prices", &json!({ "required": ["symbol"] }))
+        .unwrap()
+        .build::<(), ()>();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    let mut mock_client = ClientMock::create("", "/", "", "", router);
+    mock_client.handshake().await;
+
+    for data in [json!({"value": 1}), json!({"symbol": "ABC"})] {
+        let response = publish(&mock_client, "/prices", data).await;
+        assert_eq!(response["successful"], json!(false));
+    }
+
+    let response = publish(
+        &mock_client,
+        "/prices",
+        json!({"symbol": "ABC", "value": 1}),
+    )
+    .await;
+    assert_eq!(response["successful"], json!(true));
+}
+
+#[test]
+fn test_invalid_schema() {
+    let error = LongPollingServiceContextBuilder::new()
+        .json_schema("/prices/*", &json!({ "type": 42 }))
+        .unwrap_err();
+
+    assert_eq!(error.channel, "/prices/*");
+}
@@ -0,0 +1,133 @@
+use axum::http::StatusCode;
+use axum_cometd::{LongPollingServiceContext, LongPollingServiceContextBuilder, RouterBuilder};
+use core::time::Duration;
+use serde_json::{json, Value as JsonValue};
+use std::sync::Arc;
+use test_common::{ClientMock, ResponseExt as _};
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+fn build_context(
+    builder: impl FnOnce(LongPollingServiceContextBuilder) -> LongPollingServiceContextBuilder,
+) -> Arc<LongPollingServiceContext<(), ()>> {
+    builder(LongPollingServiceContextBuilder::new().timeout(TIMEOUT)).build()
+}
+
+fn build_mock_client(context: &Arc<LongPollingServiceContext<(), ()>>) -> ClientMock {
+    let router = RouterBuilder::new().build::<()>(Arc::clone(context));
+
+    ClientMock::create("", "/", "", "", router)
+}
+
+async fn publish(mock_client: &ClientMock, data: &[JsonValue]) -> Vec<JsonValue> {
+    let body = data
+        .iter()
+        .map(|data| {
+            json!({
+              "id": mock_client.next_id(),
+              "channel": "/topic",
+              "data": data,
+              "clientId": mock_client.client_id(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    mock_client
+        .send_request(mock_client.connect_endpoint(), JsonValue::from(body))
+        .await
+        .to::<Vec<JsonValue>>()
+        .await
+}
+
+#[tokio::test]
+async fn test_max_batch_length() {
+    let context = build_context(|builder| builder.max_batch_length(2));
+    let mut mock_client = build_mock_client(&context);
+    mock_client.handshake().await;
+
+    let responses = publish(&mock_client, &[json!(1), json!(2)]).await;
+    assert!(responses
+        .iter()
+        .all(|response| response["successful"] == json!(true)));
+
+    let responses = publish(&mock_client, &[json!(1), json!(2), json!(3)]).await;
+    assert_eq!(
+        responses,
+        [json!({
+          "successful": false,
+          "error": "413::batch_too_large",
+        })]
+    );
+}
+
+#[tokio::test]
+async fn test_max_request_size() {
+    let context = build_context(|builder| builder.max_request_size(1024));
+    let mut mock_client = build_mock_client(&context);
+    mock_client.handshake().await;
+
+    let body = json!([{
+      "id": mock_client.next_id(),
+      "channel": "/topic",
+      "data": "x".repeat(1024),
+      "clientId": mock_client.client_id(),
+    }]);
+    let response = mock_client
+        .send_request(mock_client.connect_endpoint(), body)
+        .await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn test_max_message_size() {
+    let context = build_context(|builder| builder.max_message_size(15));
+    let mut mock_client = build_mock_client(&context);
+    mock_client.handshake().await;
+
+    // `{"msg":"Hello"}` is 15 bytes, `{"msg":"Hello!"}` is 16 bytes.
+    let responses = publish(
+        &mock_client,
+        &[json!({"msg": "Hello"}), json!({"msg": "Hello!"})],
+    )
+    .await;
+    assert_eq!(responses[0]["successful"], json!(true));
+    assert_eq!(responses[1]["error"], json!("413::message_too_large"));
+}
+
+#[tokio::test]
+async fn test_max_subscriptions_per_client() {
+    let context = build_context(|builder| builder.max_subscriptions_per_client(2));
+    let mut mock_client = build_mock_client(&context);
+    mock_client.handshake().await;
+
+    mock_client
+        .subscribe(&["/topic0", "/topic0"])
+        .await
+        .unwrap();
+    mock_client.subscribe(&["/topic1"]).await.unwrap();
+    mock_client.subscribe(&["/topic1"]).await.unwrap();
+
+    let response = mock_client.subscribe(&["/topic2"]).await.unwrap_err();
+    assert_eq!(response[0]["error"], json!("403::too_many_subscriptions"));
+}
+
+#[tokio::test]
+async fn test_max_channels() {
+    let context = build_context(|builder| builder.max_channels(2));
+    let mut mock_client0 = build_mock_client(&context);
+    let mut mock_client1 = build_mock_client(&context);
+    mock_client0.handshake().await;
+    mock_client1.handshake().await;
+
+    mock_client0
+        .subscribe(&["/topic0", "/topic1"])
+        .await
+        .unwrap();
+    mock_client1
+        .subscribe(&["/topic0", "/topic1"])
+        .await
+        .unwrap();
+
+    let response = mock_client1.subscribe(&["/topic2"]).await.unwrap_err();
+    assert_eq!(response[0]["error"], json!("403::too_many_channels"));
+}
@@ -0,0 +1,124 @@
+use axum_cometd::{LongPollingServiceContext, LongPollingServiceContextBuilder, RouterBuilder};
+use core::time::Duration;
+use serde_json::json;
+use std::sync::Arc;
+use test_common::ClientMock;
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+const TTL: Duration = Duration::from_millis(100);
+
+fn build_mock_client(context: &Arc<LongPollingServiceContext<(), ()>>) -> ClientMock {
+    let router = RouterBuilder::new().build::<()>(Arc::clone(context));
+
+    ClientMock::create("", "/", "", "", router)
+}
+
+#[tokio::test]
+async fn test_send_with_ttl() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .build();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/rides/42"]).await.unwrap();
+
+    context
+        .send_with_ttl("/rides/42", json!("stale"), TTL)
+        .await
+        .unwrap();
+    context.send("/rides/42", json!("fresh")).await.unwrap();
+    tokio::time::sleep(TTL * 2).await;
+
+    assert_eq!(
+        mock_client.connect().await,
+        [("/rides/42".to_owned(), json!("fresh"))]
+    );
+}
+
+#[tokio::test]
+async fn test_channel_ttl() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .message_ttl("/rides/*", TTL)
+        .build();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client
+        .subscribe(&["/rides/42", "/news"])
+        .await
+        .unwrap();
+
+    context.send("/rides/42", json!("stale")).await.unwrap();
+    context
+        .send_with_ttl("/rides/42", json!("overridden"), TIMEOUT)
+        .await
+        .unwrap();
+    context.send("/news", json!("without ttl")).await.unwrap();
+    tokio::time::sleep(TTL * 2).await;
+    context.send("/rides/42", json!("fresh")).await.unwrap();
+
+    let mut responses = Vec::new();
+    for _ in 0..3 {
+        responses.extend(mock_client.connect().await);
+    }
+    assert_eq!(
+        responses,
+        [
+            ("/rides/42".to_owned(), json!("overridden")),
+            ("/news".to_owned(), json!("without ttl")),
+            ("/rides/42".to_owned(), json!("fresh")),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_expired_messages_free_queue() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .client_channel_capacity(1)
+        .build();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/rides/42"]).await.unwrap();
+
+    context
+        .send_with_ttl("/rides/42", json!("stale"), TTL)
+        .await
+        .unwrap();
+    tokio::time::sleep(TTL * 2).await;
+
+    let report = context.send("/rides/42", json!("fresh")).await.unwrap();
+    assert_eq!((report.recipients, report.blocked), (1, 0));
+    assert_eq!(
+        mock_client.connect().await,
+        [("/rides/42".to_owned(), json!("fresh"))]
+    );
+}
+
+#[tokio::test]
+async fn test_huge_ttl() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .message_ttl("/rides/*", Duration::MAX)
+        .build();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/rides/42"]).await.unwrap();
+
+    context
+        .send_with_ttl("/rides/42", json!("forever"), Duration::MAX)
+        .await
+        .unwrap();
+    context.send("/rides/42", json!("forever")).await.unwrap();
+
+    for _ in 0..2 {
+        assert_eq!(
+            mock_client.connect().await,
+            [("/rides/42".to_owned(), json!("forever"))]
+        );
+    }
+}
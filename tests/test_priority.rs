@@ -0,0 +1,134 @@
+use axum_cometd::{
+    LongPollingServiceContext, LongPollingServiceContextBuilder, Priority, RouterBuilder,
+};
+use core::time::Duration;
+use serde_json::json;
+use std::sync::Arc;
+use test_common::ClientMock;
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+fn build_mock_client(context: &Arc<LongPollingServiceContext<(), ()>>) -> ClientMock {
+    let router = RouterBuilder::new().build::<()>(Arc::clone(context));
+
+    ClientMock::create("", "/", "", "", router)
+}
+
+#[tokio::test]
+async fn test_urgent_message_overtakes_normal() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .build();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client
+        .subscribe(&["/chat", "/announcements"])
+        .await
+        .unwrap();
+
+    for i in 0..3 {
+        context.send("/chat", json!(i)).await.unwrap();
+    }
+    let report = context
+        .send_with_priority("/announcements", json!("Maintenance"), Priority::Urgent)
+        .await
+        .unwrap();
+    assert_eq!(report.recipients, 1);
+
+    assert_eq!(
+        mock_client.connect().await,
+        [("/announcements".to_owned(), json!("Maintenance"))]
+    );
+    for i in 0..3 {
+        assert_eq!(
+            mock_client.connect().await,
+            [("/chat".to_owned(), json!(i))]
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_urgent_message_to_client() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .conflate("/prices")
+        .build();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    let client_id = serde_json::from_value(json!(mock_client.client_id().unwrap())).unwrap();
+
+    context
+        .send_to_client("/prices", &client_id, json!(1))
+        .await
+        .unwrap();
+    context
+        .send_to_client_with_priority("/direct", &client_id, json!("Logout"), Priority::Urgent)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        mock_client.connect().await,
+        [("/direct".to_owned(), json!("Logout"))]
+    );
+    assert_eq!(
+        mock_client.connect().await,
+        [("/prices".to_owned(), json!(1))]
+    );
+}
+
+#[tokio::test]
+async fn test_urgent_message_wakes_up_connect() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .build();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/announcements"]).await.unwrap();
+
+    let (messages, _) = tokio::join!(mock_client.connect(), async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        context
+            .send_with_priority("/announcements", json!("Maintenance"), Priority::Urgent)
+            .await
+            .unwrap();
+    });
+    assert_eq!(
+        messages,
+        [("/announcements".to_owned(), json!("Maintenance"))]
+    );
+}
+
+#[tokio::test]
+async fn test_priorities_share_capacity() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .client_channel_capacity(2)
+        .build();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client
+        .subscribe(&["/chat", "/announcements"])
+        .await
+        .unwrap();
+
+    for i in 0..2 {
+        context.send("/chat", json!(i)).await.unwrap();
+    }
+    let report = context
+        .send_with_priority("/announcements", json!("Maintenance"), Priority::Urgent)
+        .await
+        .unwrap();
+    assert_eq!(report.recipients, 0);
+    assert_eq!(report.blocked, 1);
+
+    for i in 0..2 {
+        assert_eq!(
+            mock_client.connect().await,
+            [("/chat".to_owned(), json!(i))]
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use axum::http::StatusCode;
-use axum_cometd::{LongPollingServiceContextBuilder, RouterBuilder};
+use axum_cometd::{LongPollingServiceContextBuilder, RouterBuilder, SendError};
 use core::time::Duration;
-use serde_json::{json, Value as JsonValue};
+use serde_json::{json, value::RawValue, Value as JsonValue};
 use std::sync::{
     atomic::{AtomicU64, Ordering},
     Arc,
@@ -176,3 +176,34 @@ async fn test_publish() {
     assert_eq!(resp2, [msg2]);
     assert_eq!(resp3, []);
 }
+
+#[tokio::test]
+async fn test_send_raw() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .build::<(), ()>();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    let mut mock_client0 = ClientMock::create("", "/", "", "", router.clone());
+    let mut mock_client1 = ClientMock::create("", "/", "", "", router);
+
+    mock_client0.handshake().await;
+    mock_client1.handshake().await;
+    mock_client0.subscribe(&["/topic"]).await.unwrap();
+    mock_client1.subscribe(&["/**"]).await.unwrap();
+
+    let message = RawValue::from_string(r#"{"msg":"Hello","num":42}"#.to_owned()).unwrap();
+    context.send_raw("/topic", message.into()).await.unwrap();
+
+    for mock_client in [&mock_client0, &mock_client1] {
+        assert_eq!(
+            mock_client.connect().await,
+            [("/topic".to_owned(), json!({"msg": "Hello", "num": 42}))]
+        );
+    }
+
+    let message = RawValue::from_string("null".to_owned()).unwrap();
+    assert!(matches!(
+        context.send_raw("/topic/*", message.into()).await,
+        Err(SendError::InvalidChannel)
+    ));
+}
@@ -0,0 +1,140 @@
+use axum_cometd::{
+    LongPollingServiceContext, LongPollingServiceContextBuilder, RouterBuilder, SendError,
+};
+use core::time::Duration;
+use serde_json::json;
+use std::sync::Arc;
+use test_common::ClientMock;
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+fn build_context(capacity: usize) -> Arc<LongPollingServiceContext<(), ()>> {
+    LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .client_channel_capacity(capacity)
+        .publisher_channel_capacity(capacity)
+        .build()
+}
+
+fn build_mock_client(context: &Arc<LongPollingServiceContext<(), ()>>) -> ClientMock {
+    let router = RouterBuilder::new().build::<()>(Arc::clone(context));
+
+    ClientMock::create("", "/", "", "", router)
+}
+
+#[tokio::test]
+async fn test_try_send() {
+    let context = build_context(10);
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/topic"]).await.unwrap();
+
+    context.try_send("/topic", json!("Hello")).unwrap();
+    assert_eq!(
+        mock_client.connect().await,
+        [("/topic".to_owned(), json!("Hello"))]
+    );
+
+    assert!(matches!(
+        context.try_send("/topic/**", json!("Hello")),
+        Err(SendError::InvalidChannel)
+    ));
+}
+
+#[tokio::test]
+async fn test_try_send_full() {
+    let context = build_context(1);
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/topic"]).await.unwrap();
+
+    // Publisher task doesn't run until we yield, so first message fills publisher queue.
+    context.try_send("/topic", json!(0)).unwrap();
+    assert!(matches!(
+        context.try_send("/topic", json!(1)),
+        Err(SendError::Full)
+    ));
+    assert!(matches!(
+        context
+            .publisher()
+            .send_timeout("/topic", json!(1), Duration::from_millis(50))
+            .await,
+        Ok(())
+    ));
+
+    // Client queue is full, so second message is dropped by delivery instead of blocking it.
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    context.try_send("/topic", json!(2)).unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(
+        mock_client.connect().await,
+        [("/topic".to_owned(), json!(0))]
+    );
+}
+
+#[tokio::test]
+async fn test_send_timeout() {
+    let context = build_context(1);
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/topic"]).await.unwrap();
+
+    let report = context
+        .send_timeout("/topic", json!(0), Duration::from_millis(50))
+        .await
+        .unwrap();
+    assert_eq!((report.recipients, report.blocked), (1, 0));
+
+    let report = context
+        .send_timeout("/topic", json!(1), Duration::from_millis(50))
+        .await
+        .unwrap();
+    assert_eq!((report.recipients, report.blocked), (0, 1));
+}
+
+#[tokio::test]
+async fn test_blocking_send() {
+    let context = build_context(10);
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/topic"]).await.unwrap();
+
+    let publisher = context.publisher();
+    tokio::task::spawn_blocking(move || publisher.blocking_send("/topic", json!("Hello")))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        mock_client.connect().await,
+        [("/topic".to_owned(), json!("Hello"))]
+    );
+}
+
+#[tokio::test]
+async fn test_publisher_doesnt_keep_context() {
+    let context = build_context(10);
+    let publisher = context.publisher();
+
+    drop(context);
+
+    assert!(matches!(
+        publisher.try_send("/topic", json!("Hello")),
+        Err(SendError::Closed)
+    ));
+    assert!(matches!(
+        publisher.send("/topic", json!("Hello")).await,
+        Err(SendError::Closed)
+    ));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    assert!(matches!(
+        publisher.try_send("/topic", json!("Hello")),
+        Err(SendError::Closed)
+    ));
+}
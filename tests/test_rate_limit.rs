@@ -0,0 +1,179 @@
+use axum_cometd::{LongPollingServiceContextBuilder, RateLimit, ReconnectAdvice, RouterBuilder};
+use core::time::Duration;
+use serde_json::{json, Value as JsonValue};
+use std::sync::Arc;
+use test_common::{ClientMock, ResponseExt as _};
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+fn build_mock_client(
+    builder: impl FnOnce(LongPollingServiceContextBuilder) -> LongPollingServiceContextBuilder,
+) -> ClientMock {
+    let context = builder(LongPollingServiceContextBuilder::new().timeout(TIMEOUT)).build();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+
+    ClientMock::create("", "/", "", "", router)
+}
+
+async fn publish(mock_client: &ClientMock, count: usize) -> Vec<JsonValue> {
+    let body = (0..count)
+        .map(|_| {
+            json!({
+              "id": mock_client.next_id(),
+              "channel": "/topic",
+              "data": {"msg": "Hello"},
+              "clientId": mock_client.client_id(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    mock_client
+        .send_request(mock_client.connect_endpoint(), JsonValue::from(body))
+        .await
+        .to::<Vec<JsonValue>>()
+        .await
+}
+
+fn errors(responses: &[JsonValue]) -> Vec<&JsonValue> {
+    responses
+        .iter()
+        .map(|response| &response["error"])
+        .collect()
+}
+
+#[tokio::test]
+async fn test_messages_per_second() {
+    let mut mock_client = build_mock_client(|builder| {
+        builder.client_rate_limit(RateLimit::new().messages_per_second(2))
+    });
+    mock_client.handshake().await;
+
+    assert_eq!(
+        errors(&publish(&mock_client, 3).await),
+        [
+            &JsonValue::Null,
+            &JsonValue::Null,
+            &json!("429::rate_limit_exceeded")
+        ]
+    );
+
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    assert_eq!(errors(&publish(&mock_client, 1).await), [&JsonValue::Null]);
+}
+
+#[tokio::test]
+async fn test_bytes_per_second() {
+    let mut mock_client = build_mock_client(|builder| {
+        builder.browser_rate_limit(RateLimit::new().bytes_per_second(20))
+    });
+    mock_client.handshake().await;
+
+    // `{"msg":"Hello"}` is 15 bytes.
+    assert_eq!(
+        errors(&publish(&mock_client, 2).await),
+        [&JsonValue::Null, &json!("429::rate_limit_exceeded")]
+    );
+}
+
+#[tokio::test]
+async fn test_subscribe_rate_limit() {
+    let mut mock_client = build_mock_client(|builder| {
+        builder.browser_rate_limit(RateLimit::new().messages_per_second(1))
+    });
+    mock_client.handshake().await;
+
+    mock_client.subscribe(&["/topic0"]).await.unwrap();
+    let json_body = mock_client.subscribe(&["/topic1"]).await.unwrap_err();
+    assert_eq!(
+        json_body,
+        json!([{
+            "id": mock_client.last_id(),
+            "channel": "/meta/subscribe",
+            "error": "429::rate_limit_exceeded",
+            "successful": false
+        }])
+    );
+}
+
+#[tokio::test]
+async fn test_evict_on_rate_limit() {
+    let mut mock_client = build_mock_client(|builder| {
+        builder
+            .client_rate_limit(RateLimit::new().messages_per_second(1))
+            .evict_on_rate_limit(2, ReconnectAdvice::None)
+    });
+    mock_client.handshake().await;
+
+    assert_eq!(
+        errors(&publish(&mock_client, 3).await),
+        [
+            &JsonValue::Null,
+            &json!("429::rate_limit_exceeded"),
+            &json!("429::rate_limit_exceeded")
+        ]
+    );
+
+    let json_body = mock_client.subscribe(&["/topic"]).await.unwrap_err();
+    assert_eq!(json_body[0]["error"], "402::session_unknown");
+}
+
+#[tokio::test]
+async fn test_big_batch_is_one_violation() {
+    let mut mock_client = build_mock_client(|builder| {
+        builder
+            .max_batch_length(2)
+            .client_rate_limit(RateLimit::new().messages_per_second(100))
+            .evict_on_rate_limit(2, ReconnectAdvice::None)
+    });
+    mock_client.handshake().await;
+
+    assert_eq!(
+        errors(&publish(&mock_client, 3).await),
+        [&json!("413::batch_too_large")]
+    );
+    // Client wasn't evicted by first batch.
+    assert_eq!(errors(&publish(&mock_client, 1).await), [&JsonValue::Null]);
+
+    // Allowed message resets violations, so it takes two more batches to evict client.
+    publish(&mock_client, 3).await;
+    publish(&mock_client, 3).await;
+    let json_body = mock_client.subscribe(&["/topic"]).await.unwrap_err();
+    assert_eq!(json_body[0]["error"], "402::session_unknown");
+}
+
+#[tokio::test]
+async fn test_big_batch_evicts_without_rate_limit() {
+    let mut mock_client = build_mock_client(|builder| {
+        builder
+            .max_batch_length(2)
+            .evict_on_rate_limit(2, ReconnectAdvice::None)
+    });
+    mock_client.handshake().await;
+
+    publish(&mock_client, 3).await;
+    // Allowed message resets violations.
+    assert_eq!(errors(&publish(&mock_client, 1).await), [&JsonValue::Null]);
+    publish(&mock_client, 3).await;
+    publish(&mock_client, 3).await;
+    let json_body = mock_client.subscribe(&["/topic"]).await.unwrap_err();
+    assert_eq!(json_body[0]["error"], "402::session_unknown");
+}
+
+#[tokio::test]
+async fn test_message_bigger_than_bytes_per_second() {
+    let mut mock_client = build_mock_client(|builder| {
+        builder
+            .client_rate_limit(RateLimit::new().bytes_per_second(10))
+            .evict_on_rate_limit(1, ReconnectAdvice::None)
+    });
+    mock_client.handshake().await;
+
+    // `{"msg":"Hello"}` is 15 bytes and can never pass, so it isn't a violation.
+    for _ in 0..2 {
+        assert_eq!(
+            errors(&publish(&mock_client, 1).await),
+            [&json!("413::message_too_large")]
+        );
+    }
+    mock_client.subscribe(&["/topic"]).await.unwrap();
+}
@@ -11,8 +11,7 @@ async fn test_different_paths() {
     let builder = LongPollingServiceContextBuilder::new()
         .timeout(Duration::from_secs(1))
         .max_interval(Duration::from_secs(60))
-        .client_channel_capacity(10)
-        .subscription_channel_capacity(10);
+        .client_channel_capacity(10);
     let _ = format!("{builder:?}");
     let context = builder.build::<(), ()>();
 
@@ -37,7 +36,7 @@ async fn test_different_paths() {
         .subscribe(&["/SUPER_IMPORTANT_CHANNEL"])
         .await
         .unwrap();
-    let (response, ()) = try_join!(
+    let (response, _) = try_join!(
         async { Ok(mock_client.connect().await) },
         context.send(
             "/SUPER_IMPORTANT_CHANNEL",
@@ -61,7 +60,6 @@ async fn test_event_channel() {
         .timeout(Duration::from_secs(5))
         .max_interval(Duration::from_secs(60))
         .client_channel_capacity(10)
-        .subscription_channel_capacity(10)
         .build();
     let mut rx = context.rx();
 
@@ -0,0 +1,116 @@
+use axum_cometd::{
+    LongPollingServiceContext, LongPollingServiceContextBuilder, RouterBuilder, SendError,
+};
+use core::time::Duration;
+use serde_json::json;
+use std::sync::Arc;
+use test_common::ClientMock;
+use tokio::time::Instant;
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+const DELAY: Duration = Duration::from_millis(100);
+
+fn build_context() -> Arc<LongPollingServiceContext<(), ()>> {
+    LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .build()
+}
+
+fn build_mock_client(context: &Arc<LongPollingServiceContext<(), ()>>) -> ClientMock {
+    let router = RouterBuilder::new().build::<()>(Arc::clone(context));
+
+    ClientMock::create("", "/", "", "", router)
+}
+
+#[tokio::test]
+async fn test_send_after() {
+    let context = build_context();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/topic"]).await.unwrap();
+
+    let start = Instant::now();
+    let handle = context.send_after("/topic", json!("Hello"), DELAY).unwrap();
+    assert!(handle.is_pending());
+
+    assert_eq!(
+        mock_client.connect().await,
+        [("/topic".to_owned(), json!("Hello"))]
+    );
+    assert!(start.elapsed() >= DELAY);
+    assert!(!handle.is_pending());
+    assert!(!handle.cancel());
+    assert!(context.scheduled_messages().is_empty());
+}
+
+#[tokio::test]
+async fn test_send_at_cancel() {
+    let context = build_context();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/topic"]).await.unwrap();
+
+    let now = Instant::now();
+    let cancelled = context
+        .send_at("/topic", json!("Cancelled"), now + DELAY)
+        .unwrap();
+    let handle = context
+        .send_at("/topic", json!("Hello"), now + DELAY * 2)
+        .unwrap();
+
+    let scheduled = context.scheduled_messages();
+    assert_eq!(
+        scheduled
+            .iter()
+            .map(|message| (message.id, &*message.channel, message.send_at))
+            .collect::<Vec<_>>(),
+        [
+            (cancelled.id(), "/topic", now + DELAY),
+            (handle.id(), "/topic", now + DELAY * 2),
+        ]
+    );
+
+    assert!(cancelled.cancel());
+    assert!(!cancelled.is_pending());
+    assert_eq!(context.scheduled_messages().len(), 1);
+
+    assert_eq!(
+        mock_client.connect().await,
+        [("/topic".to_owned(), json!("Hello"))]
+    );
+}
+
+#[tokio::test]
+async fn test_scheduled_invalid_channel() {
+    let context = build_context();
+
+    assert!(matches!(
+        context.send_after("/topic/*", json!("Hello"), DELAY),
+        Err(SendError::InvalidChannel)
+    ));
+    assert!(context.scheduled_messages().is_empty());
+}
+
+#[tokio::test]
+async fn test_send_after_huge_delay() {
+    let context = build_context();
+    let handle = context
+        .send_after("/topic", json!("Hello"), Duration::MAX)
+        .unwrap();
+
+    assert!(handle.is_pending());
+    assert!(handle.cancel());
+}
+
+#[tokio::test]
+async fn test_scheduled_dropped_with_context() {
+    let context = build_context();
+    let handle = context.send_after("/topic", json!("Hello"), DELAY).unwrap();
+
+    drop(context);
+
+    assert!(!handle.is_pending());
+    assert!(!handle.cancel());
+}
@@ -0,0 +1,134 @@
+use axum_cometd::{
+    ClientId, LongPollingServiceContext, LongPollingServiceContextBuilder, RouterBuilder, SendError,
+};
+use core::time::Duration;
+use serde_json::json;
+use std::sync::Arc;
+use test_common::ClientMock;
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+fn build_context() -> Arc<LongPollingServiceContext<(), ()>> {
+    LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .build()
+}
+
+fn build_mock_client(context: &Arc<LongPollingServiceContext<(), ()>>) -> ClientMock {
+    let router = RouterBuilder::new().build::<()>(Arc::clone(context));
+
+    ClientMock::create("", "/", "", "", router)
+}
+
+fn client_id(mock_client: &ClientMock) -> ClientId {
+    serde_json::from_value(json!(mock_client.client_id().unwrap())).unwrap()
+}
+
+#[tokio::test]
+async fn test_send_many() {
+    let context = build_context();
+    let mut mock_client0 = build_mock_client(&context);
+    let mut mock_client1 = build_mock_client(&context);
+
+    mock_client0.handshake().await;
+    mock_client1.handshake().await;
+    mock_client0.subscribe(&["/users/0"]).await.unwrap();
+    mock_client1
+        .subscribe(&["/users/1", "/users/*"])
+        .await
+        .unwrap();
+
+    let mut report = context
+        .send_many(
+            &["/users/0", "/users/1", "/users/2", "/users/1"],
+            json!("Hello"),
+        )
+        .await
+        .unwrap();
+    report.channels.sort();
+    assert_eq!(report.channels, ["/users/*", "/users/0", "/users/1"]);
+    assert_eq!(report.recipients, 4);
+
+    assert_eq!(
+        mock_client0.connect().await,
+        [("/users/0".to_owned(), json!("Hello"))]
+    );
+
+    let mut responses = Vec::new();
+    for _ in 0..3 {
+        responses.extend(mock_client1.connect().await);
+    }
+    responses.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+    assert_eq!(
+        responses,
+        [
+            ("/users/0".to_owned(), json!("Hello")),
+            ("/users/1".to_owned(), json!("Hello")),
+            ("/users/2".to_owned(), json!("Hello")),
+        ]
+    );
+
+    assert!(matches!(
+        context
+            .send_many(&["/users/0", "/users/*"], json!("Hello"))
+            .await,
+        Err(SendError::InvalidChannel)
+    ));
+}
+
+#[tokio::test]
+async fn test_send_to_clients() {
+    let context = build_context();
+    let mut mock_client0 = build_mock_client(&context);
+    let mut mock_client1 = build_mock_client(&context);
+
+    mock_client0.handshake().await;
+    mock_client1.handshake().await;
+
+    let report = context
+        .send_to_clients(
+            "/direct",
+            &[
+                client_id(&mock_client0),
+                client_id(&mock_client1),
+                client_id(&mock_client1),
+                serde_json::from_value(json!("0".repeat(40))).unwrap(),
+            ],
+            json!("Hello"),
+        )
+        .await
+        .unwrap();
+    assert_eq!((report.recipients, report.dropped), (2, 1));
+
+    for mock_client in [&mock_client0, &mock_client1] {
+        assert_eq!(
+            mock_client.connect().await,
+            [("/direct".to_owned(), json!("Hello"))]
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_broadcast_all() {
+    let context = build_context();
+    let mut mock_client0 = build_mock_client(&context);
+    let mut mock_client1 = build_mock_client(&context);
+
+    mock_client0.handshake().await;
+    mock_client1.handshake().await;
+    mock_client0.subscribe(&["/topic"]).await.unwrap();
+
+    let report = context
+        .broadcast_all("/announcements", json!("Hello"))
+        .await
+        .unwrap();
+    assert_eq!(report.recipients, 2);
+    assert!(report.channels.is_empty());
+
+    for mock_client in [&mock_client0, &mock_client1] {
+        assert_eq!(
+            mock_client.connect().await,
+            [("/announcements".to_owned(), json!("Hello"))]
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use axum::Extension;
+use axum_cometd::{
+    ClientId, LongPollingServiceContext, LongPollingServiceContextBuilder, RouterBuilder,
+};
+use core::time::Duration;
+use serde_json::json;
+use std::sync::Arc;
+use test_common::ClientMock;
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq)]
+struct Region(&'static str);
+
+fn build_mock_client(
+    context: &Arc<LongPollingServiceContext<Region, ()>>,
+    region: &'static str,
+) -> ClientMock {
+    let router = RouterBuilder::new()
+        .build_with_additional_data(Arc::clone(context))
+        .layer(Extension(Region(region)));
+
+    ClientMock::create("", "/", "", "", router)
+}
+
+#[tokio::test]
+async fn test_send_where() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .build::<Region, ()>();
+    let mut mock_client0 = build_mock_client(&context, "eu");
+    let mut mock_client1 = build_mock_client(&context, "us");
+    let mut mock_client2 = build_mock_client(&context, "eu");
+
+    let mut mock_client3 = build_mock_client(&context, "eu");
+
+    mock_client0.handshake().await;
+    mock_client1.handshake().await;
+    mock_client2.handshake().await;
+    mock_client3.handshake().await;
+    mock_client0.subscribe(&["/news"]).await.unwrap();
+    mock_client1.subscribe(&["/news"]).await.unwrap();
+    mock_client2.subscribe(&["/*"]).await.unwrap();
+    // Not subscribed on `/news`, so never matches.
+    mock_client3.subscribe(&["/weather"]).await.unwrap();
+
+    let excluded: ClientId =
+        serde_json::from_value(json!(mock_client2.client_id().unwrap())).unwrap();
+
+    let report = context
+        .send_where(
+            "/news",
+            |client_id, attributes| {
+                *client_id != excluded
+                    && attributes.get::<Region>().as_deref() == Some(&Region("eu"))
+            },
+            json!("Hello, Europe"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(report.recipients, 1);
+
+    assert_eq!(
+        mock_client0.connect().await,
+        [("/news".to_owned(), json!("Hello, Europe"))]
+    );
+
+    let report = context
+        .send_where("/news", |_, _| true, json!("Hello, World"))
+        .await
+        .unwrap();
+    assert_eq!(report.recipients, 3);
+    assert_eq!(report.channels.len(), 2);
+
+    for mock_client in [&mock_client0, &mock_client1, &mock_client2] {
+        assert_eq!(
+            mock_client.connect().await,
+            [("/news".to_owned(), json!("Hello, World"))]
+        );
+    }
+    assert!(mock_client3.connect().await.is_empty());
+}
@@ -0,0 +1,106 @@
+use axum_cometd::{
+    ClientId, Event, LongPollingServiceContextBuilder, RouterBuilder, SubscribeError,
+};
+use core::time::Duration;
+use serde_json::json;
+use std::sync::Arc;
+use test_common::{ClientMock, TEST_CLIENT_ID};
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+fn parse_client_id(client_id: &str) -> ClientId {
+    serde_json::from_value(json!(client_id)).unwrap()
+}
+
+#[tokio::test]
+async fn test_subscribe_and_unsubscribe_client() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .max_subscriptions_per_client(1)
+        .build::<(), ()>();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    let mut mock_client = ClientMock::create("", "/", "", "", router);
+    let mut rx = context.rx();
+
+    mock_client.handshake().await;
+    let client_id = parse_client_id(mock_client.client_id().unwrap());
+    let orders = ["/orders/42".to_owned()];
+
+    context.subscribe_client(client_id, &orders).await.unwrap();
+    context
+        .send("/orders/42", json!({"status": "placed"}))
+        .await
+        .unwrap();
+    assert_eq!(
+        mock_client.connect().await,
+        [("/orders/42".to_owned(), json!({"status": "placed"}))]
+    );
+
+    assert!(matches!(
+        context
+            .subscribe_client(client_id, &["/orders/43".to_owned()])
+            .await,
+        Err(SubscribeError::TooManySubscriptions)
+    ));
+
+    context
+        .unsubscribe_client(client_id, &orders)
+        .await
+        .unwrap();
+    context
+        .subscribe_client(client_id, &["/orders/43".to_owned()])
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        *rx.recv().await.unwrap(),
+        Event::SessionAdded { client_id: id, .. } if id == client_id
+    ));
+    assert!(matches!(
+        *rx.recv().await.unwrap(),
+        Event::ServerSubscribe { client_id: id, ref channels } if id == client_id && channels == &orders
+    ));
+    assert!(matches!(
+        *rx.recv().await.unwrap(),
+        Event::ServerUnsubscribe { client_id: id, ref channels } if id == client_id && channels == &orders
+    ));
+}
+
+#[tokio::test]
+async fn test_subscribe_client_errors() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .build::<(), ()>();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    let mut mock_client = ClientMock::create("", "/", "", "", router);
+
+    mock_client.handshake().await;
+    let client_id = parse_client_id(mock_client.client_id().unwrap());
+
+    assert!(matches!(
+        context
+            .subscribe_client(client_id, &["/orders/*/items".to_owned()])
+            .await,
+        Err(SubscribeError::InvalidChannel)
+    ));
+    assert!(matches!(
+        context
+            .unsubscribe_client(client_id, &["orders".to_owned()])
+            .await,
+        Err(SubscribeError::InvalidChannel)
+    ));
+
+    let unknown_client_id = parse_client_id(TEST_CLIENT_ID);
+    assert!(matches!(
+        context
+            .subscribe_client(unknown_client_id, &["/orders/42".to_owned()])
+            .await,
+        Err(SubscribeError::ClientWasntFound(id)) if id == unknown_client_id
+    ));
+    assert!(matches!(
+        context
+            .unsubscribe_client(unknown_client_id, &["/orders/42".to_owned()])
+            .await,
+        Err(SubscribeError::ClientWasntFound(id)) if id == unknown_client_id
+    ));
+}
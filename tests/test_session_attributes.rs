@@ -0,0 +1,56 @@
+use axum::Extension;
+use axum_cometd::{ClientId, Event, LongPollingServiceContextBuilder, RouterBuilder};
+use core::time::Duration;
+use serde_json::json;
+use std::sync::Arc;
+use test_common::ClientMock;
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq)]
+struct UserName(&'static str);
+
+#[derive(Debug, PartialEq)]
+struct Counter(usize);
+
+fn parse_client_id(client_id: &str) -> ClientId {
+    serde_json::from_value(json!(client_id)).unwrap()
+}
+
+#[tokio::test]
+async fn test_session_attributes() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .build::<UserName, ()>();
+    let router = RouterBuilder::new()
+        .build_with_additional_data(Arc::clone(&context))
+        .layer(Extension(UserName("alice")));
+    let mut mock_client = ClientMock::create("", "/", "", "", router);
+    let mut rx = context.rx();
+
+    mock_client.handshake().await;
+    let client_id = parse_client_id(mock_client.client_id().unwrap());
+
+    let session = context.session(&client_id).await.unwrap();
+    assert_eq!(
+        session.get::<UserName>().as_deref(),
+        Some(&UserName("alice"))
+    );
+    assert!(!session.contains::<Counter>());
+
+    assert_eq!(session.insert(Counter(1)), None);
+    assert_eq!(session.insert(Counter(2)).as_deref(), Some(&Counter(1)));
+
+    mock_client.disconnect().await;
+    assert!(context.session(&client_id).await.is_none());
+
+    assert!(matches!(
+        *rx.recv().await.unwrap(),
+        Event::SessionAdded { client_id: id, .. } if id == client_id
+    ));
+    assert!(matches!(
+        *rx.recv().await.unwrap(),
+        Event::SessionRemoved { client_id: id, ref attributes }
+            if id == client_id && attributes.get::<Counter>().as_deref() == Some(&Counter(2))
+    ));
+}
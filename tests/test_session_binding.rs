@@ -0,0 +1,134 @@
+use axum::http::HeaderName;
+use axum_cometd::{LongPollingServiceContextBuilder, RouterBuilder, SessionBinding};
+use core::time::Duration;
+use serde_json::{json, Value as JsonValue};
+use std::sync::Arc;
+use test_common::{ClientMock, ResponseExt as _};
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+const DEVICE_HEADER: &str = "x-device-id";
+
+fn build_mock_client(session_binding: SessionBinding) -> ClientMock {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .session_binding(session_binding)
+        .build();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+
+    ClientMock::create("", "/", "", "", router)
+}
+
+fn handshake_body(mock_client: &ClientMock) -> JsonValue {
+    json!([{
+      "id": mock_client.next_id(),
+      "version": "1.0",
+      "minimumVersion": "1.0",
+      "channel": "/meta/handshake",
+      "supportedConnectionTypes": [ "long-polling" ],
+    }])
+}
+
+fn subscribe_body(mock_client: &ClientMock, client_id: &JsonValue, ext: JsonValue) -> JsonValue {
+    json!([{
+      "id": mock_client.next_id(),
+      "channel": "/meta/subscribe",
+      "subscription": ["/topic"],
+      "clientId": client_id,
+      "ext": ext,
+    }])
+}
+
+#[tokio::test]
+async fn test_header_binding() {
+    let mock_client = build_mock_client(SessionBinding::Header(HeaderName::from_static(
+        DEVICE_HEADER,
+    )));
+
+    let response = mock_client
+        .send_request(
+            mock_client.handshake_endpoint(),
+            handshake_body(&mock_client),
+        )
+        .await
+        .to_json()
+        .await;
+    assert_eq!(response[0]["error"], "402::session_unknown");
+
+    let response = mock_client
+        .send_request_with_header(
+            mock_client.handshake_endpoint(),
+            (DEVICE_HEADER, "device-0"),
+            handshake_body(&mock_client),
+        )
+        .await
+        .to_json()
+        .await;
+    assert_eq!(response[0]["successful"], true);
+    let client_id = &response[0]["clientId"];
+
+    let response = mock_client
+        .send_request_with_header(
+            mock_client.subscribe_endpoint(),
+            (DEVICE_HEADER, "device-0"),
+            subscribe_body(&mock_client, client_id, JsonValue::Null),
+        )
+        .await
+        .to_json()
+        .await;
+    assert_eq!(response[0]["successful"], true);
+
+    let response = mock_client
+        .send_request_with_header(
+            mock_client.subscribe_endpoint(),
+            (DEVICE_HEADER, "device-1"),
+            subscribe_body(&mock_client, client_id, JsonValue::Null),
+        )
+        .await
+        .to_json()
+        .await;
+    assert_eq!(response[0]["error"], "402::session_unknown");
+}
+
+#[tokio::test]
+async fn test_client_id_binding_with_token() {
+    let mock_client = build_mock_client(SessionBinding::ClientId {
+        secret: Some(Arc::from(b"secret".as_slice())),
+    });
+
+    let response = mock_client
+        .send_request(
+            mock_client.handshake_endpoint(),
+            handshake_body(&mock_client),
+        )
+        .await
+        .to_json()
+        .await;
+    assert_eq!(response[0]["successful"], true);
+    let client_id = &response[0]["clientId"];
+    let token = response[0]["ext"]["token"].as_str().unwrap();
+
+    for (ext, successful) in [
+        (JsonValue::Null, false),
+        (json!({ "token": "00" }), false),
+        (json!({ "token": "not a token" }), false),
+        (json!({ "token": token }), true),
+    ] {
+        let response = mock_client
+            .send_request(
+                mock_client.subscribe_endpoint(),
+                subscribe_body(&mock_client, client_id, ext),
+            )
+            .await
+            .to_json()
+            .await;
+        assert_eq!(response[0]["successful"], successful, "{response}");
+    }
+}
+
+#[tokio::test]
+async fn test_client_id_binding_without_token() {
+    let mut mock_client = build_mock_client(SessionBinding::ClientId { secret: None });
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/topic"]).await.unwrap();
+}
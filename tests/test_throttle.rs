@@ -0,0 +1,169 @@
+use axum_cometd::{
+    Event, LongPollingServiceContext, LongPollingServiceContextBuilder, RouterBuilder, Throttle,
+};
+use core::time::Duration;
+use serde_json::json;
+use std::sync::Arc;
+use test_common::ClientMock;
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+const INTERVAL: Duration = Duration::from_millis(200);
+
+fn build_mock_client(context: &Arc<LongPollingServiceContext<(), ()>>) -> ClientMock {
+    let router = RouterBuilder::new().build::<()>(Arc::clone(context));
+
+    ClientMock::create("", "/", "", "", router)
+}
+
+async fn recv_suppressed(context: &Arc<LongPollingServiceContext<(), ()>>) -> (String, usize) {
+    let mut rx = context.rx();
+
+    loop {
+        if let Event::MessagesSuppressed { ref channel, count } = *rx.recv().await.unwrap() {
+            return (channel.clone(), count);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_drop_excess() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .throttle("/prices/*", Throttle::new(2, INTERVAL))
+        .build();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/prices/*"]).await.unwrap();
+
+    let suppressed = tokio::spawn({
+        let context = Arc::clone(&context);
+        async move { recv_suppressed(&context).await }
+    });
+    tokio::task::yield_now().await;
+
+    let mut throttled = Vec::new();
+    for i in 0..5 {
+        let report = context.send("/prices/AAPL", json!(i)).await.unwrap();
+        throttled.push(report.throttled);
+    }
+    // Every channel matching pattern is throttled separately.
+    let report = context.send("/prices/MSFT", json!(0)).await.unwrap();
+    assert!(!report.throttled);
+
+    assert_eq!(throttled, [false, false, true, true, true]);
+
+    let mut responses = Vec::new();
+    for _ in 0..3 {
+        responses.extend(mock_client.connect().await);
+    }
+    assert_eq!(
+        responses,
+        [
+            ("/prices/AAPL".to_owned(), json!(0)),
+            ("/prices/AAPL".to_owned(), json!(1)),
+            ("/prices/MSFT".to_owned(), json!(0)),
+        ]
+    );
+    // Client requests arm timer, which reports suppressed messages at the end of interval.
+    assert_eq!(suppressed.await.unwrap(), ("/prices/AAPL".to_owned(), 3));
+
+    let report = context.send("/prices/AAPL", json!(5)).await.unwrap();
+    assert!(!report.throttled);
+}
+
+#[tokio::test]
+async fn test_merge_latest() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .throttle("/prices", Throttle::new(1, INTERVAL).merge_latest())
+        .build();
+    let mut mock_client = build_mock_client(&context);
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/prices"]).await.unwrap();
+
+    for i in 0..5 {
+        context.send("/prices", json!(i)).await.unwrap();
+    }
+
+    assert_eq!(
+        mock_client.connect().await,
+        [("/prices".to_owned(), json!(0))]
+    );
+    assert_eq!(
+        mock_client.connect().await,
+        [("/prices".to_owned(), json!(4))]
+    );
+}
+
+#[tokio::test]
+async fn test_direct_sends_arent_throttled() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .throttle("/prices", Throttle::new(1, INTERVAL).merge_latest())
+        .build();
+    let mut alice = build_mock_client(&context);
+    let mut bob = build_mock_client(&context);
+
+    alice.handshake().await;
+    bob.handshake().await;
+    alice.subscribe(&["/prices"]).await.unwrap();
+    bob.subscribe(&["/prices"]).await.unwrap();
+    let alice_id = serde_json::from_value(json!(alice.client_id().unwrap())).unwrap();
+
+    let report = context.send("/prices", json!("public")).await.unwrap();
+    assert!(!report.throttled);
+    context
+        .send_to_client("/prices", &alice_id, json!("secret for alice"))
+        .await
+        .unwrap();
+    let report = context
+        .send_to_clients("/prices", &[alice_id], json!("another secret"))
+        .await
+        .unwrap();
+    assert_eq!((report.recipients, report.throttled), (1, false));
+
+    let mut responses = Vec::new();
+    for _ in 0..3 {
+        responses.extend(alice.connect().await);
+    }
+    assert_eq!(
+        responses,
+        [
+            ("/prices".to_owned(), json!("public")),
+            ("/prices".to_owned(), json!("secret for alice")),
+            ("/prices".to_owned(), json!("another secret")),
+        ]
+    );
+
+    // Window ends without merged message: direct sends never reach other subscribers.
+    assert_eq!(
+        bob.connect().await,
+        [("/prices".to_owned(), json!("public"))]
+    );
+    tokio::time::sleep(INTERVAL * 2).await;
+    assert!(bob.connect().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_next_send_reports_suppressed() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .throttle("/prices", Throttle::new(1, INTERVAL))
+        .build::<(), ()>();
+    let mut rx = context.rx();
+
+    for i in 0..3 {
+        context.send("/prices", json!(i)).await.unwrap();
+    }
+    tokio::time::sleep(INTERVAL).await;
+
+    let report = context.send("/news", json!(0)).await.unwrap();
+    assert!(!report.throttled);
+
+    let Event::MessagesSuppressed { ref channel, count } = *rx.recv().await.unwrap() else {
+        panic!("Expected MessagesSuppressed event");
+    };
+    assert_eq!((channel.as_str(), count), ("/prices", 2));
+}
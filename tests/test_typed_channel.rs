@@ -0,0 +1,144 @@
+use axum_cometd::{LongPollingServiceContextBuilder, RouterBuilder, TypedChannel};
+use core::time::Duration;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use std::sync::Arc;
+use test_common::{ClientMock, ResponseExt as _};
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Price {
+    symbol: String,
+    value: u64,
+}
+
+const PRICES: TypedChannel<Price> = TypedChannel::new("/prices");
+
+async fn publish(mock_client: &ClientMock, data: JsonValue) -> JsonValue {
+    let body = json!([{
+      "id": mock_client.next_id(),
+      "channel": PRICES.name(),
+      "data": data,
+      "clientId": mock_client.client_id(),
+    }]);
+
+    mock_client
+        .send_request(mock_client.connect_endpoint(), body)
+        .await
+        .to::<[JsonValue; 1]>()
+        .await
+        .into_iter()
+        .next()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_typed_channel() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .typed_channel(&PRICES)
+        .build::<(), ()>();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    let mut mock_client = ClientMock::create("", "/", "", "", router);
+
+    let mut rx = PRICES.subscribe(&context).await.unwrap();
+    mock_client.handshake().await;
+    mock_client.subscribe(&[PRICES.name()]).await.unwrap();
+
+    let report = PRICES
+        .send(
+            &context,
+            &Price {
+                symbol: "ABC".to_owned(),
+                value: 1,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(report.recipients, 2);
+    assert_eq!(
+        rx.recv().await,
+        Some(Price {
+            symbol: "ABC".to_owned(),
+            value: 1,
+        })
+    );
+    assert_eq!(
+        mock_client.connect().await,
+        [(
+            PRICES.name().to_owned(),
+            json!({"symbol": "ABC", "value": 1})
+        )]
+    );
+
+    let response = publish(&mock_client, json!({"symbol": "DEF", "value": "2"})).await;
+    assert_eq!(response["error"], json!("400::invalid_payload"));
+
+    let response = publish(&mock_client, json!({"symbol": "DEF", "value": 2})).await;
+    assert_eq!(response["successful"], json!(true));
+    assert_eq!(
+        rx.recv().await,
+        Some(Price {
+            symbol: "DEF".to_owned(),
+            value: 2,
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_untyped_message_is_skipped() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .build::<(), ()>();
+
+    let mut rx = PRICES.subscribe(&context).await.unwrap();
+
+    context
+        .send(PRICES.name(), json!("Not a price"))
+        .await
+        .unwrap();
+    PRICES
+        .send(
+            &context,
+            &Price {
+                symbol: "ABC".to_owned(),
+                value: 1,
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        rx.recv().await,
+        Some(Price {
+            symbol: "ABC".to_owned(),
+            value: 1,
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_local_session_doesnt_time_out() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .max_interval(Duration::from_millis(300))
+        .build::<(), ()>();
+
+    let mut rx = PRICES.subscribe(&context).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(800)).await;
+
+    let price = Price {
+        symbol: "ABC".to_owned(),
+        value: 1,
+    };
+    let report = PRICES.send(&context, &price).await.unwrap();
+    assert_eq!(report.recipients, 1);
+    assert_eq!(rx.recv().await, Some(price));
+
+    drop(rx);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let report = context.send(PRICES.name(), json!("Hello")).await.unwrap();
+    assert_eq!(report.recipients, 0);
+}
@@ -0,0 +1,148 @@
+use axum_cometd::{ClientId, LongPollingServiceContextBuilder, RouterBuilder};
+use core::time::Duration;
+use serde_json::{json, Value as JsonValue};
+use std::sync::Arc;
+use test_common::{ClientMock, ResponseExt as _};
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+async fn handshake(mock_client: &ClientMock, user: &str) -> ClientId {
+    let response = mock_client
+        .send_request(
+            mock_client.handshake_endpoint(),
+            json!([{
+              "id": mock_client.next_id(),
+              "version": "1.0",
+              "minimumVersion": "1.0",
+              "channel": "/meta/handshake",
+              "supportedConnectionTypes": [ "long-polling" ],
+              "ext": { "user": user },
+            }]),
+        )
+        .await
+        .to_json()
+        .await;
+
+    serde_json::from_value(response[0]["clientId"].clone()).unwrap()
+}
+
+async fn connect(mock_client: &ClientMock, client_id: ClientId) -> JsonValue {
+    mock_client
+        .send_request(
+            mock_client.connect_endpoint(),
+            json!([{
+              "id": mock_client.next_id(),
+              "channel": "/meta/connect",
+              "connectionType": "long-polling",
+              "clientId": client_id,
+            }]),
+        )
+        .await
+        .to_json()
+        .await
+}
+
+#[tokio::test]
+async fn test_send_to_user_and_disconnect_user() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .user_identity(|_, ext, _| ext.and_then(|ext| ext["user"].as_str()).map(str::to_owned))
+        .build::<(), ()>();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    let mock_client = ClientMock::create("", "/", "", "", router);
+
+    let alice0 = handshake(&mock_client, "alice").await;
+    let alice1 = handshake(&mock_client, "alice").await;
+    let bob = handshake(&mock_client, "bob").await;
+
+    let mut sessions = context.user_sessions("alice");
+    sessions.sort_by_key(ToString::to_string);
+    let mut expected = vec![alice0, alice1];
+    expected.sort_by_key(ToString::to_string);
+    assert_eq!(sessions, expected);
+    assert_eq!(context.user_sessions("bob"), [bob]);
+    assert!(context.user_sessions("carol").is_empty());
+
+    assert_eq!(
+        context
+            .send_to_user("alice", "/notifications", json!({"msg": "Hello"}))
+            .await
+            .unwrap(),
+        2
+    );
+    for client_id in [alice0, alice1] {
+        let response = connect(&mock_client, client_id).await;
+        assert_eq!(
+            response[0],
+            json!({
+              "channel": "/notifications",
+              "data": {"msg": "Hello"},
+            })
+        );
+    }
+
+    assert_eq!(context.disconnect_user("alice").await, 2);
+    assert!(context.user_sessions("alice").is_empty());
+    assert!(context.session(&alice0).await.is_none());
+    assert!(context.session(&alice1).await.is_none());
+    assert!(context.session(&bob).await.is_some());
+    assert_eq!(
+        context
+            .send_to_user("alice", "/notifications", json!({"msg": "Hello"}))
+            .await
+            .unwrap(),
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_disconnect_user_completes_pending_connect() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .user_identity(|_, ext, _| ext.and_then(|ext| ext["user"].as_str()).map(str::to_owned))
+        .build::<(), ()>();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    let mock_client = ClientMock::create("", "/", "", "", router);
+
+    let alice = handshake(&mock_client, "alice").await;
+
+    let (response, count) = tokio::join!(connect(&mock_client, alice), async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        context.disconnect_user("alice").await
+    });
+    assert_eq!(count, 1);
+    assert_eq!(response[0]["successful"], json!(true));
+    assert_eq!(response[0]["advice"], json!({"reconnect": "none"}));
+    assert!(context.session(&alice).await.is_none());
+}
+
+#[tokio::test]
+async fn test_send_to_user_skips_full_session() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(TIMEOUT)
+        .client_channel_capacity(1)
+        .user_identity(|_, ext, _| ext.and_then(|ext| ext["user"].as_str()).map(str::to_owned))
+        .build::<(), ()>();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    let mock_client = ClientMock::create("", "/", "", "", router);
+
+    let alice0 = handshake(&mock_client, "alice").await;
+    let alice1 = handshake(&mock_client, "alice").await;
+
+    context
+        .send_to_client("/notifications", &alice0, json!({"msg": "First"}))
+        .await
+        .unwrap();
+    assert_eq!(
+        context
+            .send_to_user("alice", "/notifications", json!({"msg": "Hello"}))
+            .await
+            .unwrap(),
+        1
+    );
+
+    let response = connect(&mock_client, alice0).await;
+    assert_eq!(response[0]["data"], json!({"msg": "First"}));
+    let response = connect(&mock_client, alice1).await;
+    assert_eq!(response[0]["data"], json!({"msg": "Hello"}));
+}
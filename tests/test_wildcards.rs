@@ -36,7 +36,7 @@ async fn test_single_wildcard() {
     mock_client.subscribe(&["/*"]).await.unwrap();
 
     let test_message = gen_message();
-    let (responses, ()) = try_join!(
+    let (responses, _) = try_join!(
         async { Ok(mock_client.connect().await) },
         context.send("/topic", &test_message),
     )
@@ -44,7 +44,7 @@ async fn test_single_wildcard() {
     assert_eq!(responses, vec![("/topic".to_owned(), test_message)]);
 
     let test_message = gen_message();
-    let (responses, ()) = try_join!(
+    let (responses, _) = try_join!(
         async { Ok(mock_client.connect().await) },
         context.send("/topic/second", &test_message),
     )
@@ -62,7 +62,7 @@ async fn test_all_wildcard() {
     mock_client.subscribe(&["/**"]).await.unwrap();
 
     let test_message = gen_message();
-    let (responses, ()) = try_join!(
+    let (responses, _) = try_join!(
         async { Ok(mock_client.connect().await) },
         context.send("/topic", &test_message),
     )
@@ -70,7 +70,7 @@ async fn test_all_wildcard() {
     assert_eq!(responses, vec![("/topic".to_owned(), test_message)]);
 
     let test_message = gen_message();
-    let (responses, ()) = try_join!(
+    let (responses, _) = try_join!(
         async { Ok(mock_client.connect().await) },
         context.send("/topic/second", &test_message),
     )
@@ -78,7 +78,7 @@ async fn test_all_wildcard() {
     assert_eq!(responses, vec![("/topic/second".to_owned(), test_message)]);
 
     let test_message = gen_message();
-    let (responses, ()) = try_join!(
+    let (responses, _) = try_join!(
         async { Ok(mock_client.connect().await) },
         context.send("/first/second/third", &test_message),
     )
@@ -99,7 +99,7 @@ async fn test_nested_single_wildcard() {
     mock_client.subscribe(&["/topic/*"]).await.unwrap();
 
     let test_message = gen_message();
-    let (responses, ()) = try_join!(
+    let (responses, _) = try_join!(
         async { Ok(mock_client.connect().await) },
         context.send("/topic/", &test_message),
     )
@@ -107,7 +107,7 @@ async fn test_nested_single_wildcard() {
     assert_eq!(responses, vec![("/topic/".to_owned(), test_message)]);
 
     let test_message = gen_message();
-    let (responses, ()) = try_join!(
+    let (responses, _) = try_join!(
         async { Ok(mock_client.connect().await) },
         context.send("/topic/second", &test_message),
     )
@@ -115,7 +115,7 @@ async fn test_nested_single_wildcard() {
     assert_eq!(responses, vec![("/topic/second".to_owned(), test_message)]);
 
     let test_message = gen_message();
-    let (responses, ()) = try_join!(
+    let (responses, _) = try_join!(
         async { Ok(mock_client.connect().await) },
         context.send("/topic/second/third", &test_message),
     )
@@ -133,7 +133,7 @@ async fn test_nested_all_wildcard() {
     mock_client.subscribe(&["/topic/**"]).await.unwrap();
 
     let test_message = gen_message();
-    let (responses, ()) = try_join!(
+    let (responses, _) = try_join!(
         async { Ok(mock_client.connect().await) },
         context.send("/topic/", &test_message),
     )
@@ -141,7 +141,7 @@ async fn test_nested_all_wildcard() {
     assert_eq!(responses, vec![("/topic/".to_owned(), test_message)]);
 
     let test_message = gen_message();
-    let (responses, ()) = try_join!(
+    let (responses, _) = try_join!(
         async { Ok(mock_client.connect().await) },
         context.send("/topic/second", &test_message),
     )
@@ -149,7 +149,7 @@ async fn test_nested_all_wildcard() {
     assert_eq!(responses, vec![("/topic/second".to_owned(), test_message)]);
 
     let test_message = gen_message();
-    let (responses, ()) = try_join!(
+    let (responses, _) = try_join!(
         async { Ok(mock_client.connect().await) },
         context.send("/topic/second/third", &test_message),
     )
@@ -160,10 +160,83 @@ async fn test_nested_all_wildcard() {
     );
 
     let test_message = gen_message();
-    let (responses, ()) = try_join!(
+    let (responses, _) = try_join!(
         async { Ok(mock_client.connect().await) },
         context.send("/qwe", &test_message),
     )
     .unwrap();
     assert_eq!(responses, vec![]);
 }
+
+// sub: /foo/*, /foo/**
+// send: /foo/bar
+#[tokio::test]
+async fn test_overlapping_wildcards_deliver_once() {
+    let (context, mut mock_client) = build_context_and_mock_client();
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/foo/*", "/foo/**"]).await.unwrap();
+
+    let test_message = gen_message();
+    let (responses, _) = try_join!(
+        async { Ok(mock_client.connect().await) },
+        context.send("/foo/bar", &test_message),
+    )
+    .unwrap();
+    assert_eq!(responses, vec![("/foo/bar".to_owned(), test_message)]);
+
+    assert_eq!(mock_client.connect().await, vec![]);
+}
+
+// sub: /foo/bar, /foo/**, /**
+// send: /foo/bar
+#[tokio::test]
+async fn test_channel_and_wildcards_deliver_once() {
+    let (context, mut mock_client) = build_context_and_mock_client();
+
+    mock_client.handshake().await;
+    mock_client.subscribe(&["/foo/bar"]).await.unwrap();
+    mock_client.subscribe(&["/foo/**", "/**"]).await.unwrap();
+
+    let test_message = gen_message();
+    let (responses, _) = try_join!(
+        async { Ok(mock_client.connect().await) },
+        context.send("/foo/bar", &test_message),
+    )
+    .unwrap();
+    assert_eq!(responses, vec![("/foo/bar".to_owned(), test_message)]);
+
+    assert_eq!(mock_client.connect().await, vec![]);
+}
+
+// client0 sub: /foo/bar, /foo/*
+// client1 sub: /foo/**
+// send: /foo/bar
+#[tokio::test]
+async fn test_every_client_gets_message_once() {
+    let context = LongPollingServiceContextBuilder::new()
+        .timeout(Duration::from_secs(1))
+        .build::<(), ()>();
+    let router = RouterBuilder::new().build::<()>(Arc::clone(&context));
+    let mut mock_client0 = ClientMock::create("", "/", "", "", router.clone());
+    let mut mock_client1 = ClientMock::create("", "/", "", "", router);
+
+    mock_client0.handshake().await;
+    mock_client1.handshake().await;
+    mock_client0
+        .subscribe(&["/foo/bar", "/foo/*"])
+        .await
+        .unwrap();
+    mock_client1.subscribe(&["/foo/**"]).await.unwrap();
+
+    let test_message = gen_message();
+    context.send("/foo/bar", &test_message).await.unwrap();
+
+    for mock_client in [&mock_client0, &mock_client1] {
+        assert_eq!(
+            mock_client.connect().await,
+            vec![("/foo/bar".to_owned(), test_message.clone())]
+        );
+        assert_eq!(mock_client.connect().await, vec![]);
+    }
+}